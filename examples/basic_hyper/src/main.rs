@@ -1,24 +1,28 @@
 use bytes::Bytes;
+use futures::StreamExt;
 use http_body_util::Full;
 use hyper_tls::HttpsConnector;
 use hyper_util::client::legacy::connect::HttpConnector;
 use hyper_util::client::legacy::Client;
 use katal_github_client::client::http::HttpGithubClient;
+use katal_github_client::client::retry::{Retry, RetryLayer, RetryPolicy};
 use katal_github_client::common::project::{ProjectSlug, RepositoryRef};
 use katal_github_client::common::release::Release;
 use katal_github_client::common::Page;
 use katal_github_client::compact_str::CompactString;
 use katal_github_client::context::{Context, GithubUrl};
+use katal_github_client::paginate::Paginate;
 use katal_github_client::query::get_project_release_list::GetProjectReleaseListQuery;
-use katal_github_client::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
 use katal_github_client::url::Url;
-use katal_github_client::{GithubAuth, GithubClient, UserAgent};
+use katal_github_client::{GithubAuth, UserAgent};
+use std::pin::pin;
+use tower_layer::Layer;
 
 #[tokio::main]
 async fn main() {
   let authentication: Option<GithubAuth> = None;
-  // if let Some(token) = std::env::var("GITHUB_PRIVATE_TOKEN").ok() {
-  //   Some(GithubAuth::PrivateToken(token.parse().unwrap()))
+  // if let Some(token) = std::env::var("GITHUB_TOKEN").ok() {
+  //   Some(GithubAuth::Bearer(token.parse().unwrap()))
   // } else {
   //   None
   // };
@@ -26,12 +30,14 @@ async fn main() {
   let connector = HttpsConnector::new();
   let client: Client<HttpsConnector<_>, Full<Bytes>> =
     Client::builder(hyper_util::rt::TokioExecutor::new()).build(connector);
-  let mut client: HttpGithubClient<Client<HttpsConnector<HttpConnector>, Full<Bytes>>> = HttpGithubClient::new(client);
+  let client: Retry<Client<HttpsConnector<HttpConnector>, Full<Bytes>>> =
+    RetryLayer::new(RetryPolicy::default()).layer(client);
+  let mut client = HttpGithubClient::new(client);
   let context = Context::new()
     .set_github_url(GithubUrl(Url::parse("https://api.github.com/").unwrap()))
     .set_user_agent(UserAgent::from_static("katal_github_client_example/0.0.0"));
   // let mut query = GetProjectListQuery::<_>::new().set_context(context);
-  // // query.auth = Some(GithubAuth::PrivateToken("...".parse().unwrap()));
+  // // query.auth = Some(GithubAuth::Bearer("...".parse().unwrap()));
   // // query.owned = Some(true);
   // let res = client.get_project_list(&query).await.unwrap();
   // for p in &res.items {
@@ -52,14 +58,10 @@ async fn main() {
     )))
     .set_context(context.clone());
     query.auth = authentication.clone();
-    let mut res: Page<Release> = client.get_project_release_list(&query).await.unwrap();
-    eprintln!("successfully fetched first page. count={:?}", res.items.len());
-    // eprintln!("successfully fetched first page. last={:?}", res.last);
-    while let Some(next) = res.next {
-      let mut query = GetProjectReleaseListPageQuery::<_, _>::new(next).set_context(context.clone());
-      query.auth = authentication.clone();
-      res = client.get_project_release_list_page(&query).await.unwrap();
-      eprintln!("successfully got page. count={:?}", res.items.len());
+    let mut pages = pin!(Paginate::new(&mut client, query).pages());
+    while let Some(page) = pages.next().await {
+      let page: Page<Release> = page.unwrap();
+      eprintln!("successfully got page. count={:?}", page.items.len());
     }
   }
 }
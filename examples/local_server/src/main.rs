@@ -0,0 +1,291 @@
+//! Local stand-in for the GitHub REST API, serving releases and assets from a state directory.
+//!
+//! See `README.md` for the usage and the layout of the state directory.
+
+mod server;
+mod state;
+
+use crate::server::{Options, Server};
+use crate::state::State;
+use http_body_util::BodyExt;
+use hyper::body::Incoming;
+use hyper::service::service_fn;
+use hyper::Request;
+use hyper_util::rt::TokioIo;
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use url::Url;
+
+const USAGE: &str =
+  "usage: katal_github_client_local_server <STATE_DIR> [--port <PORT>] [--rate-limit <LIMIT>] [--token <TOKEN>]";
+
+struct Args {
+  state_dir: PathBuf,
+  port: u16,
+  options: Options,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+  let mut state_dir: Option<PathBuf> = None;
+  let mut port: u16 = 0;
+  let mut options = Options::default();
+  while let Some(arg) = args.next() {
+    let mut value = |name: &str| args.next().ok_or_else(|| format!("missing value for {name}"));
+    match arg.as_str() {
+      "--port" => port = value("--port")?.parse().map_err(|e| format!("invalid port: {e}"))?,
+      "--rate-limit" => {
+        options.rate_limit = value("--rate-limit")?
+          .parse()
+          .map_err(|e| format!("invalid rate limit: {e}"))?
+      }
+      "--token" => options.token = Some(value("--token")?),
+      _ if state_dir.is_none() && !arg.starts_with("--") => state_dir = Some(PathBuf::from(arg)),
+      _ => return Err(format!("unexpected argument: {arg}")),
+    }
+  }
+  Ok(Args {
+    state_dir: state_dir.ok_or("missing state directory")?,
+    port,
+    options,
+  })
+}
+
+/// Load the state and bind the server, returning its base URL and a future running it.
+async fn start(args: Args) -> std::io::Result<(Url, impl std::future::Future<Output = std::io::Result<Infallible>>)> {
+  let listener = TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], args.port))).await?;
+  let base = Url::parse(&format!("http://{}/", listener.local_addr()?)).expect("server URL is valid");
+  let state = State::load(&args.state_dir, &base)?;
+  let server = Arc::new(Server::new(base.clone(), state, args.options));
+  Ok((base, serve(listener, server)))
+}
+
+async fn serve(listener: TcpListener, server: Arc<Server>) -> std::io::Result<Infallible> {
+  loop {
+    let (stream, _) = listener.accept().await?;
+    let server = Arc::clone(&server);
+    tokio::spawn(async move {
+      let service = service_fn(move |req: Request<Incoming>| {
+        let server = Arc::clone(&server);
+        async move {
+          let (parts, body) = req.into_parts();
+          let body = body.collect().await?.to_bytes();
+          Ok::<_, hyper::Error>(server.handle(&Request::from_parts(parts, body)))
+        }
+      });
+      if let Err(e) = hyper::server::conn::http1::Builder::new()
+        .serve_connection(TokioIo::new(stream), service)
+        .await
+      {
+        eprintln!("connection error: {e}");
+      }
+    });
+  }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+  let args = match parse_args(std::env::args().skip(1)) {
+    Ok(args) => args,
+    Err(e) => {
+      eprintln!("{e}\n{USAGE}");
+      return ExitCode::from(2);
+    }
+  };
+  let (base, server) = match start(args).await {
+    Ok(started) => started,
+    Err(e) => {
+      eprintln!("failed to start server: {e}");
+      return ExitCode::FAILURE;
+    }
+  };
+  println!("{base}");
+  match server.await {
+    Ok(never) => match never {},
+    Err(e) => {
+      eprintln!("server failure: {e}");
+      ExitCode::FAILURE
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use bytes::Bytes;
+  use futures::TryStreamExt;
+  use katal_github_client::client::http::HttpGithubClientError;
+  use katal_github_client::client::reqwest::ReqwestGithubClient;
+  use katal_github_client::common::project::{ProjectSlug, RepositoryRef};
+  use katal_github_client::common::release::{InputRelease, ReleaseAssetId};
+  use katal_github_client::common::Pagination;
+  use katal_github_client::compact_str::CompactString;
+  use katal_github_client::context::{Context, GithubUrl};
+  use katal_github_client::paginate::Paginate;
+  use katal_github_client::query::create_release::CreateReleaseQuery;
+  use katal_github_client::query::download_release_asset::DownloadReleaseAssetQuery;
+  use katal_github_client::query::get_project_release_list::GetProjectReleaseListQuery;
+  use katal_github_client::query::upload_release_asset::UploadReleaseAssetQuery;
+  use katal_github_client::{GithubAuth, GithubClient, UserAgent};
+  use std::num::NonZeroU8;
+
+  /// Start a server with the `octocat/Hello-World` fixture, returning its base URL
+  async fn hello_world(options: Options) -> (Url, tempfile::TempDir) {
+    let dir = tempfile::tempdir().unwrap();
+    let repo = dir.path().join("repos/octocat/Hello-World");
+    std::fs::create_dir_all(repo.join("assets/v1.0.0")).unwrap();
+    std::fs::copy(
+      "../../test-resources/fake/octocat-hello-world/releases.json",
+      repo.join("releases.json"),
+    )
+    .unwrap();
+    std::fs::write(
+      repo.join("assets/v1.0.0/hello-v1.0.0-x86_64-unknown-linux-gnu.tar.gz"),
+      "hello",
+    )
+    .unwrap();
+    let args = Args {
+      state_dir: dir.path().to_path_buf(),
+      port: 0,
+      options,
+    };
+    let (base, server) = start(args).await.unwrap();
+    tokio::spawn(server);
+    (base, dir)
+  }
+
+  fn query(base: &Url) -> GetProjectReleaseListQuery<Context<GithubUrl, UserAgent>, CompactString> {
+    let context = Context::new()
+      .set_github_url(GithubUrl(base.clone()))
+      .set_user_agent(UserAgent::from_static("katal_github_client_test/0.0.0"));
+    let mut query = GetProjectReleaseListQuery::new(RepositoryRef::Slug(ProjectSlug::new(
+      CompactString::new("octocat"),
+      CompactString::new("Hello-World"),
+    )))
+    .set_context(context);
+    query.pagination = Some(Pagination {
+      per_page: NonZeroU8::new(3),
+    });
+    query
+  }
+
+  #[tokio::test]
+  async fn paginate_releases() {
+    let (base, _dir) = hello_world(Options::default()).await;
+    let mut client = ReqwestGithubClient::from_reqwest(katal_github_client::reqwest::Client::new());
+    let query = query(&base);
+
+    let first = (&mut client).get_project_release_list(&query).await.unwrap();
+    assert_eq!(
+      first.next.as_deref(),
+      Some(format!("{base}repos/octocat/Hello-World/releases?per_page=3&page=2").as_str())
+    );
+    assert_eq!(first.rate_limit.unwrap().remaining, 4999);
+    assert!(first.items[0].url.as_str().starts_with(base.as_str()));
+
+    let tags: Vec<String> = Paginate::new(&mut client, query)
+      .items()
+      .map_ok(|release| release.tag_name)
+      .try_collect()
+      .await
+      .unwrap();
+    assert_eq!(tags, ["v1.2.0", "v1.1.0", "v1.1.0-rc.1", "v1.0.0"]);
+  }
+
+  #[tokio::test]
+  async fn reject_bad_credentials_and_exhausted_rate_limit() {
+    let options = Options {
+      rate_limit: 2,
+      token: Some(String::from("ghp_local")),
+    };
+    let (base, _dir) = hello_world(options).await;
+    let mut client = ReqwestGithubClient::from_reqwest(katal_github_client::reqwest::Client::new());
+    let mut query = query(&base);
+
+    query.auth = Some(GithubAuth::Bearer(CompactString::new("ghp_other")));
+    assert!(matches!(
+      (&mut client).get_project_release_list(&query).await,
+      Err(HttpGithubClientError::Forbidden(Some(_)))
+    ));
+    query.auth = Some(GithubAuth::Bearer(CompactString::new("ghp_local")));
+    assert!((&mut client).get_project_release_list(&query).await.is_ok());
+    assert!(matches!(
+      (&mut client).get_project_release_list(&query).await,
+      Err(HttpGithubClientError::RateLimited { .. })
+    ));
+  }
+
+  #[tokio::test]
+  async fn download_asset() {
+    let (base, _dir) = hello_world(Options::default()).await;
+    let mut client = ReqwestGithubClient::from_reqwest(katal_github_client::reqwest::Client::new());
+    let context = Context::new()
+      .set_github_url(GithubUrl(base.clone()))
+      .set_user_agent(UserAgent::from_static("katal_github_client_test/0.0.0"));
+    let mut query = DownloadReleaseAssetQuery::new(
+      RepositoryRef::Slug(ProjectSlug::new(
+        CompactString::new("octocat"),
+        CompactString::new("Hello-World"),
+      )),
+      ReleaseAssetId::new(5011),
+    )
+    .set_context(context);
+
+    let download = (&mut client).download_release_asset(&query).await.unwrap();
+    let content: Vec<_> = download.content.try_collect().await.unwrap();
+    assert_eq!(content.concat(), b"hello");
+
+    query.offset = Some(2);
+    let download = (&mut client).download_release_asset(&query).await.unwrap();
+    assert_eq!((download.offset, download.size), (2, Some(5)));
+    let content: Vec<_> = download.content.try_collect().await.unwrap();
+    assert_eq!(content.concat(), b"llo");
+  }
+
+  #[tokio::test]
+  async fn publish_release() {
+    let (base, _dir) = hello_world(Options::default()).await;
+    let mut client = ReqwestGithubClient::from_reqwest(katal_github_client::reqwest::Client::new());
+    let context = Context::new()
+      .set_github_url(GithubUrl(base.clone()))
+      .set_user_agent(UserAgent::from_static("katal_github_client_test/0.0.0"));
+    let repository = RepositoryRef::Slug(ProjectSlug::new(
+      CompactString::new("octocat"),
+      CompactString::new("Hello-World"),
+    ));
+    let create = CreateReleaseQuery::new(repository.clone(), InputRelease::new(CompactString::new("v2.0.0")))
+      .set_context(context.clone());
+
+    let release = (&mut client).create_release(&create).await.unwrap();
+    assert_eq!(release.tag_name, "v2.0.0");
+    assert!(matches!(
+      (&mut client).create_release(&create).await,
+      Err(HttpGithubClientError::Conflict(_))
+    ));
+
+    let upload = UploadReleaseAssetQuery::new(
+      release.upload_url.clone(),
+      CompactString::new("hello v2.tar.gz"),
+      CompactString::new("application/gzip"),
+      Bytes::from_static(b"hello v2"),
+    )
+    .set_context(context.clone());
+    let asset = (&mut client).upload_release_asset(&upload).await.unwrap();
+    assert_eq!((asset.name.as_str(), asset.size), ("hello v2.tar.gz", 8));
+    assert!(matches!(
+      (&mut client).upload_release_asset(&upload).await,
+      Err(HttpGithubClientError::Conflict(_))
+    ));
+
+    let latest = (&mut client).get_project_release_list(&query(&base)).await.unwrap();
+    assert_eq!(latest.items[0].assets.len(), 1);
+    assert_eq!(latest.items[0].assets[0], asset);
+    let download = DownloadReleaseAssetQuery::new(repository, asset.id).set_context(context);
+    let download = (&mut client).download_release_asset(&download).await.unwrap();
+    let content: Vec<_> = download.content.try_collect().await.unwrap();
+    assert_eq!(content.concat(), b"hello v2");
+  }
+}
@@ -0,0 +1,436 @@
+use crate::state::{Repository, State};
+use bytes::Bytes;
+use http_body_util::Full;
+use hyper::header::{HeaderValue, ACCEPT, AUTHORIZATION, CONTENT_RANGE, CONTENT_TYPE, LINK, LOCATION, RANGE};
+use hyper::{Method, Request, Response, StatusCode};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use serde_json::{json, Value};
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use url::Url;
+
+/// Default number of items per page, same as GitHub
+const DEFAULT_PER_PAGE: usize = 30;
+const MAX_PER_PAGE: usize = 100;
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60 * 60);
+/// Characters escaped in URL path segments, such as tags and asset names
+const SEGMENT: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+#[derive(Debug, Clone)]
+pub struct Options {
+  /// Number of requests allowed per rate limit window
+  pub rate_limit: u64,
+  /// Only token accepted in the `Authorization` header; anonymous requests are always allowed.
+  pub token: Option<String>,
+}
+
+impl Default for Options {
+  fn default() -> Self {
+    Self {
+      rate_limit: 5000,
+      token: None,
+    }
+  }
+}
+
+#[derive(Debug)]
+pub struct Server {
+  base: Url,
+  state: Mutex<State>,
+  options: Options,
+  rate_limit: Mutex<RateLimit>,
+}
+
+#[derive(Debug)]
+struct RateLimit {
+  used: u64,
+  reset: SystemTime,
+}
+
+type Res = Response<Full<Bytes>>;
+
+impl Server {
+  pub fn new(base: Url, state: State, options: Options) -> Self {
+    Self {
+      base,
+      state: Mutex::new(state),
+      options,
+      rate_limit: Mutex::new(RateLimit {
+        used: 0,
+        reset: SystemTime::now() + RATE_LIMIT_WINDOW,
+      }),
+    }
+  }
+
+  pub fn handle(&self, req: &Request<Bytes>) -> Res {
+    let (used, reset, exhausted) = {
+      let mut rate_limit = self.rate_limit.lock().expect("rate limit state is not poisoned");
+      let now = SystemTime::now();
+      if now >= rate_limit.reset {
+        rate_limit.used = 0;
+        rate_limit.reset = now + RATE_LIMIT_WINDOW;
+      }
+      let exhausted = rate_limit.used >= self.options.rate_limit;
+      if !exhausted {
+        rate_limit.used += 1;
+      }
+      (rate_limit.used, rate_limit.reset, exhausted)
+    };
+
+    let mut res = if exhausted {
+      error(StatusCode::FORBIDDEN, "API rate limit exceeded for 127.0.0.1.")
+    } else if !self.is_authorized(req) {
+      error(StatusCode::UNAUTHORIZED, "Bad credentials")
+    } else {
+      self.route(req)
+    };
+
+    let reset = reset.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+    let headers = res.headers_mut();
+    headers.insert("x-ratelimit-limit", self.options.rate_limit.into());
+    headers.insert("x-ratelimit-remaining", (self.options.rate_limit - used).into());
+    headers.insert("x-ratelimit-used", used.into());
+    headers.insert("x-ratelimit-reset", reset.into());
+    headers.insert("x-ratelimit-resource", HeaderValue::from_static("core"));
+    res
+  }
+
+  fn is_authorized<B>(&self, req: &Request<B>) -> bool {
+    let (Some(token), Some(header)) = (&self.options.token, req.headers().get(AUTHORIZATION)) else {
+      return true;
+    };
+    let header = header.to_str().unwrap_or_default();
+    ["Bearer ", "token "]
+      .iter()
+      .any(|scheme| header.strip_prefix(scheme) == Some(token.as_str()))
+  }
+
+  fn route(&self, req: &Request<Bytes>) -> Res {
+    let url = match self.base.join(req.uri().path_and_query().map_or("/", |p| p.as_str())) {
+      Ok(url) => url,
+      Err(_) => return not_found(),
+    };
+    let segments: Vec<&str> = url.path_segments().map(Iterator::collect).unwrap_or_default();
+    if req.method() == Method::POST {
+      return match segments.as_slice() {
+        ["repos", owner, repo, "releases"] => self.with_repo(owner, repo, |repository| {
+          self.create_release(owner, repo, repository, req.body())
+        }),
+        ["repos", owner, repo, "releases", id, "assets"] => {
+          self.with_repo(owner, repo, |repository| match id.parse() {
+            Ok(id) => self.upload_asset(&url, owner, repo, repository, id, req),
+            Err(_) => not_found(),
+          })
+        }
+        _ => not_found(),
+      };
+    }
+    if req.method() != Method::GET {
+      return not_found();
+    }
+    match segments.as_slice() {
+      ["repos", owner, repo, "releases"] => {
+        self.with_repo(owner, repo, |repository| self.page(&url, &repository.releases))
+      }
+      ["repos", owner, repo, "releases", "assets", id] => self.with_repo(owner, repo, |repository| {
+        let Some((_, asset)) = id.parse().ok().and_then(|id| repository.asset(id)) else {
+          return not_found();
+        };
+        let wants_content = req
+          .headers()
+          .get(ACCEPT)
+          .is_some_and(|accept| accept.as_bytes() == b"application/octet-stream");
+        match (wants_content, asset["browser_download_url"].as_str()) {
+          (true, Some(location)) => Response::builder()
+            .status(StatusCode::FOUND)
+            .header(LOCATION, location)
+            .body(Full::new(Bytes::new()))
+            .unwrap(),
+          _ => json_response(StatusCode::OK, asset),
+        }
+      }),
+      ["repos", owner, repo, "releases", "latest"] => self.with_repo(owner, repo, |repository| {
+        let latest = repository
+          .releases
+          .iter()
+          .find(|release| release["draft"] != true && release["prerelease"] != true);
+        match latest {
+          Some(release) => json_response(StatusCode::OK, release),
+          None => not_found(),
+        }
+      }),
+      ["repos", owner, repo, "releases", "tags", tag] => self.with_repo(owner, repo, |repository| {
+        let tag = percent_decode_str(tag).decode_utf8_lossy();
+        match repository.releases.iter().find(|release| release["tag_name"] == *tag) {
+          Some(release) => json_response(StatusCode::OK, release),
+          None => not_found(),
+        }
+      }),
+      ["repos", owner, repo, "releases", id] => self.with_repo(owner, repo, |repository| {
+        match id.parse().ok().and_then(|id| repository.release(id)) {
+          Some(release) => json_response(StatusCode::OK, release),
+          None => not_found(),
+        }
+      }),
+      ["repos", owner, repo, "releases", id, "assets"] => self.with_repo(owner, repo, |repository| {
+        match id.parse().ok().and_then(|id| repository.release(id)) {
+          Some(release) => self.page(&url, release["assets"].as_array().map_or(&[], Vec::as_slice)),
+          None => not_found(),
+        }
+      }),
+      [owner, repo, "releases", "download", tag, name] => self.with_repo(owner, repo, |repository| {
+        let tag = percent_decode_str(tag).decode_utf8_lossy();
+        let name = percent_decode_str(name).decode_utf8_lossy();
+        let Some(content) = repository.asset_content(&tag, &name) else {
+          return not_found();
+        };
+        // Only open-ended ranges are supported, as used to resume downloads
+        let start = req.headers().get(RANGE).and_then(|range| {
+          range
+            .to_str()
+            .ok()?
+            .strip_prefix("bytes=")?
+            .strip_suffix('-')?
+            .parse::<usize>()
+            .ok()
+        });
+        match start {
+          None => Response::builder()
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .body(Full::new(Bytes::from(content)))
+            .unwrap(),
+          Some(start) if start < content.len() => Response::builder()
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(CONTENT_TYPE, "application/octet-stream")
+            .header(
+              CONTENT_RANGE,
+              format!("bytes {start}-{}/{}", content.len() - 1, content.len()),
+            )
+            .body(Full::new(Bytes::from(content).slice(start..)))
+            .unwrap(),
+          Some(_) => Response::builder()
+            .status(StatusCode::RANGE_NOT_SATISFIABLE)
+            .header(CONTENT_RANGE, format!("bytes */{}", content.len()))
+            .body(Full::new(Bytes::new()))
+            .unwrap(),
+        }
+      }),
+      _ => not_found(),
+    }
+  }
+
+  fn with_repo(&self, owner: &str, repo: &str, f: impl FnOnce(&mut Repository) -> Res) -> Res {
+    let mut state = self.state.lock().expect("state is not poisoned");
+    match state.repository_mut(owner, repo) {
+      Some(repository) => f(repository),
+      None => not_found(),
+    }
+  }
+
+  /// Create a release from the JSON `body`, as the repository owner
+  fn create_release(&self, owner: &str, repo: &str, repository: &mut Repository, body: &[u8]) -> Res {
+    let Ok(input) = serde_json::from_slice::<Value>(body) else {
+      return error(StatusCode::BAD_REQUEST, "Problems parsing JSON");
+    };
+    let Some(tag) = input["tag_name"].as_str() else {
+      return validation_error("Release", "missing_field", "tag_name");
+    };
+    if repository.releases.iter().any(|release| release["tag_name"] == tag) {
+      return validation_error("Release", "already_exists", "tag_name");
+    }
+    let id = repository.next_id();
+    let now = now();
+    let draft = input["draft"].as_bool().unwrap_or(false);
+    let api = format!("{}repos/{owner}/{repo}", self.base);
+    let encoded_tag = utf8_percent_encode(tag, SEGMENT);
+    let release = json!({
+      "url": format!("{api}/releases/{id}"),
+      "html_url": format!("{}{owner}/{repo}/releases/tag/{encoded_tag}", self.base),
+      "assets_url": format!("{api}/releases/{id}/assets"),
+      "upload_url": format!("{api}/releases/{id}/assets{{?name,label}}"),
+      "tarball_url": format!("{api}/tarball/{encoded_tag}"),
+      "zipball_url": format!("{api}/zipball/{encoded_tag}"),
+      "id": id,
+      "node_id": format!("RE_{id}"),
+      "tag_name": tag,
+      "target_commitish": input["target_commitish"].as_str().unwrap_or("main"),
+      "name": input["name"],
+      "body": input["body"],
+      "draft": draft,
+      "prerelease": input["prerelease"].as_bool().unwrap_or(false),
+      "created_at": now,
+      "published_at": if draft { Value::Null } else { Value::from(now.as_str()) },
+      "author": self.user(owner),
+      "assets": [],
+    });
+    repository.releases.insert(0, release.clone());
+    json_response(StatusCode::CREATED, &release)
+  }
+
+  /// Add the request body as an asset of the release `release_id`, named by the `name` query parameter, as the
+  /// repository owner
+  fn upload_asset(
+    &self,
+    url: &Url,
+    owner: &str,
+    repo: &str,
+    repository: &mut Repository,
+    release_id: u64,
+    req: &Request<Bytes>,
+  ) -> Res {
+    let param = |name: &str| {
+      url
+        .query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+    };
+    let Some(name) = param("name").filter(|name| !name.is_empty()) else {
+      return validation_error("ReleaseAsset", "missing_field", "name");
+    };
+    let id = repository.next_id();
+    let Some(release) = repository.release_mut(release_id) else {
+      return not_found();
+    };
+    let (Some(tag), Some(assets)) = (
+      release["tag_name"].as_str().map(String::from),
+      release["assets"].as_array_mut(),
+    ) else {
+      return not_found();
+    };
+    if assets.iter().any(|asset| asset["name"] == name.as_str()) {
+      return validation_error("ReleaseAsset", "already_exists", "name");
+    }
+    let now = now();
+    let content_type = req
+      .headers()
+      .get(CONTENT_TYPE)
+      .and_then(|value| value.to_str().ok())
+      .unwrap_or("application/octet-stream");
+    let asset = json!({
+      "url": format!("{}repos/{owner}/{repo}/releases/assets/{id}", self.base),
+      "browser_download_url": format!(
+        "{}{owner}/{repo}/releases/download/{}/{}",
+        self.base,
+        utf8_percent_encode(&tag, SEGMENT),
+        utf8_percent_encode(&name, SEGMENT),
+      ),
+      "id": id,
+      "node_id": format!("RA_{id}"),
+      "name": name,
+      "label": param("label"),
+      "state": "uploaded",
+      "content_type": content_type,
+      "size": req.body().len(),
+      "download_count": 0,
+      "created_at": now,
+      "updated_at": now,
+      "uploader": self.user(owner),
+    });
+    assets.push(asset.clone());
+    repository.add_upload(&tag, &name, req.body().to_vec());
+    json_response(StatusCode::CREATED, &asset)
+  }
+
+  /// User attributed to the created releases and uploaded assets
+  fn user(&self, login: &str) -> Value {
+    json!({
+      "login": login,
+      "id": 1,
+      "node_id": "MDQ6VXNlcjE=",
+      "avatar_url": format!("{}images/error/{login}_happy.gif", self.base),
+      "gravatar_id": "",
+      "url": format!("{}users/{login}", self.base),
+      "html_url": format!("{}{login}", self.base),
+      "type": "User",
+      "site_admin": false,
+    })
+  }
+
+  /// Respond with a page of `items`, using the `per_page` and `page` parameters of `url`
+  fn page(&self, url: &Url, items: &[Value]) -> Res {
+    let param = |name: &str| -> Option<usize> {
+      url
+        .query_pairs()
+        .find(|(key, _)| key == name)
+        .and_then(|(_, value)| value.parse().ok())
+    };
+    let per_page = param("per_page").unwrap_or(DEFAULT_PER_PAGE).clamp(1, MAX_PER_PAGE);
+    let page = param("page").unwrap_or(1).max(1);
+    let last = items.len().div_ceil(per_page).max(1);
+
+    let cursor = |page: usize| {
+      let mut cursor = url.clone();
+      cursor
+        .query_pairs_mut()
+        .clear()
+        .append_pair("per_page", per_page.to_string().as_str())
+        .append_pair("page", page.to_string().as_str());
+      cursor
+    };
+    let mut links = Vec::new();
+    if page > 1 {
+      links.push(format!("<{}>; rel=\"prev\"", cursor(page.min(last + 1) - 1)));
+    }
+    if page < last {
+      links.push(format!("<{}>; rel=\"next\"", cursor(page + 1)));
+      links.push(format!("<{}>; rel=\"last\"", cursor(last)));
+    }
+    if page > 1 {
+      links.push(format!("<{}>; rel=\"first\"", cursor(1)));
+    }
+
+    let items: Vec<Value> = items
+      .iter()
+      .skip((page - 1) * per_page)
+      .take(per_page)
+      .cloned()
+      .collect();
+    let mut res = json_response(StatusCode::OK, &Value::Array(items));
+    if !links.is_empty() {
+      res.headers_mut().insert(LINK, links.join(", ").parse().unwrap());
+    }
+    res
+  }
+}
+
+fn json_response(status: StatusCode, body: &Value) -> Res {
+  Response::builder()
+    .status(status)
+    .header(CONTENT_TYPE, "application/json; charset=utf-8")
+    .body(Full::new(Bytes::from(
+      serde_json::to_vec(body).expect("JSON values are serializable"),
+    )))
+    .unwrap()
+}
+
+fn error(status: StatusCode, message: &str) -> Res {
+  json_response(
+    status,
+    &json!({
+      "message": message,
+      "documentation_url": "https://docs.github.com/rest",
+      "status": status.as_str(),
+    }),
+  )
+}
+
+fn not_found() -> Res {
+  error(StatusCode::NOT_FOUND, "Not Found")
+}
+
+/// `422` response for an invalid `field` of the request
+fn validation_error(resource: &str, code: &str, field: &str) -> Res {
+  json_response(
+    StatusCode::UNPROCESSABLE_ENTITY,
+    &json!({
+      "message": "Validation Failed",
+      "errors": [{ "resource": resource, "code": code, "field": field }],
+      "documentation_url": "https://docs.github.com/rest",
+      "status": "422",
+    }),
+  )
+}
+
+fn now() -> String {
+  chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
@@ -0,0 +1,159 @@
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use url::Url;
+
+/// Origins of the real GitHub, rewritten to the local server when loading the state
+const GITHUB_ORIGINS: [&str; 3] = [
+  "https://api.github.com/",
+  "https://uploads.github.com/",
+  "https://github.com/",
+];
+
+/// Repositories served by the local server, loaded from a state directory
+#[derive(Debug, Default)]
+pub struct State {
+  /// Repositories, by lowercase `owner/name`
+  repositories: BTreeMap<String, Repository>,
+}
+
+#[derive(Debug)]
+pub struct Repository {
+  /// Releases, newest first
+  pub releases: Vec<Value>,
+  assets_dir: PathBuf,
+  /// Content of the assets uploaded to the server, by release tag and asset name
+  uploads: BTreeMap<(String, String), Vec<u8>>,
+}
+
+impl State {
+  /// Load the repositories from `<dir>/repos/<owner>/<repo>/`.
+  pub fn load(dir: &Path, base: &Url) -> io::Result<Self> {
+    let mut state = Self::default();
+    let repos = dir.join("repos");
+    if !repos.is_dir() {
+      return Ok(state);
+    }
+    for owner in fs::read_dir(repos)? {
+      let owner = owner?;
+      if !owner.file_type()?.is_dir() {
+        continue;
+      }
+      for repo in fs::read_dir(owner.path())? {
+        let repo = repo?;
+        if !repo.file_type()?.is_dir() {
+          continue;
+        }
+        let key = format!(
+          "{}/{}",
+          owner.file_name().to_string_lossy(),
+          repo.file_name().to_string_lossy()
+        );
+        state
+          .repositories
+          .insert(key.to_lowercase(), Repository::load(&repo.path(), base)?);
+      }
+    }
+    Ok(state)
+  }
+
+  pub fn repository_mut(&mut self, owner: &str, name: &str) -> Option<&mut Repository> {
+    self.repositories.get_mut(&format!("{owner}/{name}").to_lowercase())
+  }
+}
+
+impl Repository {
+  fn load(dir: &Path, base: &Url) -> io::Result<Self> {
+    let releases = match fs::read(dir.join("releases.json")) {
+      Ok(raw) => {
+        let mut releases: Value = serde_json::from_slice(&raw)?;
+        rewrite_urls(&mut releases, base);
+        match releases {
+          Value::Array(releases) => releases,
+          _ => return Err(io::Error::new(io::ErrorKind::InvalidData, "releases must be an array")),
+        }
+      }
+      Err(e) if e.kind() == io::ErrorKind::NotFound => Vec::new(),
+      Err(e) => return Err(e),
+    };
+    Ok(Self {
+      releases,
+      assets_dir: dir.join("assets"),
+      uploads: BTreeMap::new(),
+    })
+  }
+
+  pub fn release(&self, id: u64) -> Option<&Value> {
+    self.releases.iter().find(|release| release["id"].as_u64() == Some(id))
+  }
+
+  /// Asset with the id `id`, along with the release containing it
+  pub fn asset(&self, id: u64) -> Option<(&Value, &Value)> {
+    self.releases.iter().find_map(|release| {
+      release["assets"]
+        .as_array()?
+        .iter()
+        .find(|asset| asset["id"].as_u64() == Some(id))
+        .map(|asset| (release, asset))
+    })
+  }
+
+  pub fn release_mut(&mut self, id: u64) -> Option<&mut Value> {
+    self
+      .releases
+      .iter_mut()
+      .find(|release| release["id"].as_u64() == Some(id))
+  }
+
+  /// Id for a new release or asset, greater than all the existing ones
+  pub fn next_id(&self) -> u64 {
+    let assets = self
+      .releases
+      .iter()
+      .flat_map(|release| release["assets"].as_array().map_or(&[][..], Vec::as_slice));
+    self
+      .releases
+      .iter()
+      .chain(assets)
+      .filter_map(|item| item["id"].as_u64())
+      .max()
+      .unwrap_or(0)
+      + 1
+  }
+
+  /// Keep the content of the asset `name` uploaded to the release `tag`, in memory
+  pub fn add_upload(&mut self, tag: &str, name: &str, content: Vec<u8>) {
+    self.uploads.insert((tag.to_string(), name.to_string()), content);
+  }
+
+  /// Content of the asset `name` in the release `tag`, uploaded or read from the assets directory
+  pub fn asset_content(&self, tag: &str, name: &str) -> Option<Vec<u8>> {
+    if let Some(content) = self.uploads.get(&(tag.to_string(), name.to_string())) {
+      return Some(content.clone());
+    }
+    self.asset_path(tag, name).and_then(|path| fs::read(path).ok())
+  }
+
+  /// Path of the content of the asset `name` in the release `tag`
+  fn asset_path(&self, tag: &str, name: &str) -> Option<PathBuf> {
+    // Reject anything that could escape the assets directory
+    let is_plain =
+      |segment: &str| !segment.is_empty() && segment != "." && segment != ".." && !segment.contains(['/', '\\']);
+    (is_plain(tag) && is_plain(name)).then(|| self.assets_dir.join(tag).join(name))
+  }
+}
+
+fn rewrite_urls(value: &mut Value, base: &Url) {
+  match value {
+    Value::String(s) => {
+      if let Some(path) = GITHUB_ORIGINS.iter().find_map(|origin| s.strip_prefix(origin)) {
+        *s = format!("{base}{path}");
+      }
+    }
+    Value::Array(items) => items.iter_mut().for_each(|item| rewrite_urls(item, base)),
+    Value::Object(fields) => fields.values_mut().for_each(|field| rewrite_urls(field, base)),
+    _ => {}
+  }
+}
@@ -0,0 +1,239 @@
+use crate::common::app::{AppId, InstallationAccessToken, InstallationId};
+use crate::query::create_installation_access_token::CreateInstallationAccessTokenQuery;
+use crate::{GithubAuth, GithubClient};
+use chrono::{DateTime, Duration, Utc};
+use compact_str::CompactString;
+use core::fmt;
+use futures::lock::Mutex;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use std::future::Future;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+pub enum GithubAppError {
+  #[error("invalid app private key: {0}")]
+  PrivateKey(String),
+  #[error("failed to sign app JWT: {0}")]
+  Sign(String),
+  #[error("failed to create installation access token: {0}")]
+  InstallationAccessToken(String),
+}
+
+/// Private key of a GitHub App, used to sign the JWT authenticating as the app itself.
+///
+/// <https://docs.github.com/en/apps/creating-github-apps/authenticating-with-a-github-app/generating-a-json-web-token-jwt-for-a-github-app>
+#[derive(Clone)]
+pub struct GithubAppKey {
+  app: AppId,
+  key: EncodingKey,
+}
+
+#[derive(serde::Serialize)]
+struct AppClaims {
+  iat: i64,
+  exp: i64,
+  iss: CompactString,
+}
+
+impl GithubAppKey {
+  /// Create the app key from the PEM-encoded RSA private key downloaded from the app settings.
+  pub fn from_rsa_pem(app: AppId, pem: &[u8]) -> Result<Self, GithubAppError> {
+    let key = EncodingKey::from_rsa_pem(pem).map_err(|e| GithubAppError::PrivateKey(format!("{e:?}")))?;
+    Ok(Self { app, key })
+  }
+
+  pub fn app(&self) -> AppId {
+    self.app
+  }
+
+  /// Sign a JWT for this app, valid at `now`.
+  ///
+  /// The token is backdated by 60 seconds to allow for clock drift, and expires after 9 minutes (GitHub rejects
+  /// tokens valid for more than 10 minutes).
+  pub fn jwt(&self, now: DateTime<Utc>) -> Result<CompactString, GithubAppError> {
+    let claims = AppClaims {
+      iat: (now - Duration::seconds(60)).timestamp(),
+      exp: (now + Duration::minutes(9)).timestamp(),
+      iss: self.app.with_str(|id| CompactString::new(id)),
+    };
+    jsonwebtoken::encode(&Header::new(Algorithm::RS256), &claims, &self.key)
+      .map(CompactString::from)
+      .map_err(|e| GithubAppError::Sign(format!("{e:?}")))
+  }
+
+  /// Auth to use for app-level requests, such as creating installation access tokens.
+  pub fn auth(&self, now: DateTime<Utc>) -> Result<GithubAuth, GithubAppError> {
+    self.jwt(now).map(GithubAuth::Bearer)
+  }
+}
+
+impl fmt::Debug for GithubAppKey {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("GithubAppKey")
+      .field("app", &self.app)
+      .finish_non_exhaustive()
+  }
+}
+
+/// Auth source for requests made as a GitHub App installation.
+///
+/// The installation access token is exchanged using the app key on first use, then cached and renewed 5 minutes
+/// before it expires. The returned auth can be set as the `auth` of any query.
+/// [`InstallationAuthLayer`](crate::client::installation_auth::InstallationAuthLayer) sets it on the queries sent without
+/// auth.
+#[derive(Debug)]
+pub struct InstallationAuth {
+  key: GithubAppKey,
+  installation: InstallationId,
+  token: Mutex<Option<InstallationAccessToken>>,
+}
+
+impl InstallationAuth {
+  pub fn new(key: GithubAppKey, installation: InstallationId) -> Self {
+    Self {
+      key,
+      installation,
+      token: Mutex::new(None),
+    }
+  }
+
+  pub fn installation(&self) -> InstallationId {
+    self.installation
+  }
+
+  /// Get the auth for the installation, creating a new access token through `client` if needed.
+  pub async fn get<Cx, C>(&self, client: C, context: &Cx) -> Result<GithubAuth, GithubAppError>
+  where
+    Cx: Clone,
+    C: GithubClient<Cx, CompactString>,
+    for<'req> C::CreateInstallationAccessTokenError<'req>: fmt::Debug,
+  {
+    self
+      .get_with(context, |query| async move {
+        client
+          .create_installation_access_token(&query)
+          .await
+          .map_err(|e| format!("{e:?}"))
+      })
+      .await
+  }
+
+  /// Get the auth for the installation, creating a new access token with `create` if needed.
+  pub(crate) async fn get_with<Cx, F, Fut>(&self, context: &Cx, create: F) -> Result<GithubAuth, GithubAppError>
+  where
+    Cx: Clone,
+    F: FnOnce(CreateInstallationAccessTokenQuery<Cx, CompactString>) -> Fut,
+    Fut: Future<Output = Result<InstallationAccessToken, String>>,
+  {
+    let now = Utc::now();
+    let mut token = self.token.lock().await;
+    if let Some(token) = token.as_ref() {
+      if now + Duration::minutes(5) < token.expires_at {
+        return Ok(GithubAuth::Bearer(token.token.clone()));
+      }
+    }
+    let mut query = CreateInstallationAccessTokenQuery::new(self.installation).set_context(context.clone());
+    query.auth = Some(self.key.auth(now)?);
+    let new_token = create(query).await.map_err(GithubAppError::InstallationAccessToken)?;
+    let auth = GithubAuth::Bearer(new_token.token.clone());
+    *token = Some(new_token);
+    Ok(auth)
+  }
+
+  /// Drop the cached access token, for example after it was revoked.
+  pub async fn clear(&self) {
+    *self.token.lock().await = None;
+  }
+}
+
+#[cfg(all(test, feature = "http"))]
+mod test {
+  use super::*;
+  use crate::client::http::HttpGithubClient;
+  use crate::context::{Context, GithubUrl};
+  use crate::UserAgent;
+  use bytes::Bytes;
+  use chrono::TimeZone;
+  use http::{Request, Response};
+  use http_body_util::Full;
+  use std::convert::Infallible;
+  use std::future::{ready, Ready};
+  use std::sync::{Arc, Mutex};
+  use std::task::Poll;
+  use tower_service::Service;
+  use url::Url;
+
+  const PRIVATE_KEY: &[u8] = include_bytes!("../test-resources/github-app/private-key.pem");
+  const PUBLIC_KEY: &[u8] = include_bytes!("../test-resources/github-app/public-key.pem");
+
+  #[derive(serde::Deserialize)]
+  struct DecodedClaims {
+    iat: i64,
+    exp: i64,
+    iss: String,
+  }
+
+  #[test]
+  fn sign_app_jwt() {
+    let key = GithubAppKey::from_rsa_pem(AppId::new(123), PRIVATE_KEY).unwrap();
+    let now = Utc.with_ymd_and_hms(2025, 1, 1, 12, 0, 0).unwrap();
+    let jwt = key.jwt(now).unwrap();
+
+    let mut validation = jsonwebtoken::Validation::new(Algorithm::RS256);
+    validation.validate_exp = false;
+    let decoded = jsonwebtoken::decode::<DecodedClaims>(
+      jwt.as_str(),
+      &jsonwebtoken::DecodingKey::from_rsa_pem(PUBLIC_KEY).unwrap(),
+      &validation,
+    )
+    .unwrap();
+    assert_eq!(decoded.claims.iss, "123");
+    assert_eq!(decoded.claims.iat, now.timestamp() - 60);
+    assert_eq!(decoded.claims.exp, now.timestamp() + 9 * 60);
+  }
+
+  /// Inner service answering every request with a new installation access token
+  #[derive(Clone, Default)]
+  struct TokenServer {
+    requests: Arc<Mutex<Vec<Request<Full<Bytes>>>>>,
+  }
+
+  impl Service<Request<Full<Bytes>>> for TokenServer {
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut std::task::Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+      let mut requests = self.requests.lock().unwrap();
+      requests.push(req);
+      let body = format!(
+        r#"{{"token":"ghs_{}","expires_at":"{}"}}"#,
+        requests.len(),
+        (Utc::now() + Duration::hours(1)).to_rfc3339()
+      );
+      ready(Ok(Response::new(Full::new(Bytes::from(body)))))
+    }
+  }
+
+  #[tokio::test]
+  async fn reuse_installation_access_token() {
+    let key = GithubAppKey::from_rsa_pem(AppId::new(123), PRIVATE_KEY).unwrap();
+    let auth = InstallationAuth::new(key, InstallationId::new(456));
+    let context = Context::new()
+      .set_github_url(GithubUrl(Url::parse("https://api.github.com/").unwrap()))
+      .set_user_agent(UserAgent::from_static("katal_github_client_test/0.0.0"));
+    let mut client = HttpGithubClient::new(TokenServer::default());
+
+    let first = auth.get(&mut client, &context).await.unwrap();
+    let second = auth.get(&mut client, &context).await.unwrap();
+    assert_eq!(first, GithubAuth::Bearer(CompactString::new("ghs_1")));
+    assert_eq!(second, first);
+
+    auth.clear().await;
+    let third = auth.get(&mut client, &context).await.unwrap();
+    assert_eq!(third, GithubAuth::Bearer(CompactString::new("ghs_2")));
+  }
+}
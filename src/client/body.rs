@@ -0,0 +1,64 @@
+use bytes::Bytes;
+use core::pin::Pin;
+use core::task::{Context, Poll};
+use http_body::{Body, Frame, SizeHint};
+use http_body_util::Full;
+
+/// Response body returned by the layers wrapping the inner service of
+/// [`HttpGithubClient`](crate::client::http::HttpGithubClient)
+///
+/// Layers only buffer the responses they need to read (e.g. to detect rate limits or store them in a cache); other
+/// responses keep the body of the inner service, so they are still streamed.
+pub struct ResponseBody<B> {
+  kind: Kind<B>,
+}
+
+enum Kind<B> {
+  Buffered(Full<Bytes>),
+  Inner(Pin<Box<B>>),
+}
+
+impl<B> ResponseBody<B> {
+  /// Body with content already received
+  pub fn buffered(content: Bytes) -> Self {
+    Self {
+      kind: Kind::Buffered(Full::new(content)),
+    }
+  }
+
+  /// Body streamed from the inner service
+  pub fn inner(body: B) -> Self {
+    Self {
+      kind: Kind::Inner(Box::pin(body)),
+    }
+  }
+}
+
+impl<B> Body for ResponseBody<B>
+where
+  B: Body<Data = Bytes>,
+{
+  type Data = Bytes;
+  type Error = B::Error;
+
+  fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, B::Error>>> {
+    match &mut self.get_mut().kind {
+      Kind::Buffered(body) => Pin::new(body).poll_frame(cx).map_err(|never| match never {}),
+      Kind::Inner(body) => body.as_mut().poll_frame(cx),
+    }
+  }
+
+  fn is_end_stream(&self) -> bool {
+    match &self.kind {
+      Kind::Buffered(body) => body.is_end_stream(),
+      Kind::Inner(body) => body.is_end_stream(),
+    }
+  }
+
+  fn size_hint(&self) -> SizeHint {
+    match &self.kind {
+      Kind::Buffered(body) => body.size_hint(),
+      Kind::Inner(body) => body.size_hint(),
+    }
+  }
+}
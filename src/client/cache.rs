@@ -0,0 +1,394 @@
+use crate::client::body::ResponseBody;
+use bytes::Bytes;
+use core::task::{Context, Poll};
+use futures::future::BoxFuture;
+use http::header::{
+  ACCEPT, AUTHORIZATION, CONTENT_ENCODING, CONTENT_LENGTH, CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+  LAST_MODIFIED, TRANSFER_ENCODING,
+};
+use http::{HeaderMap, HeaderValue, Method, Request, Response, StatusCode};
+use http_body::Body;
+use http_body_util::{BodyExt, Full};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::error::Error as StdError;
+use std::sync::{Arc, Mutex};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Key identifying a cached response
+///
+/// The auth identity is a hash of the `Authorization` header, so tokens are never kept in the store.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CacheKey {
+  pub uri: String,
+  pub auth: Option<String>,
+  pub accept: Option<String>,
+}
+
+impl CacheKey {
+  pub fn from_request<B>(req: &Request<B>) -> Self {
+    Self {
+      uri: req.uri().to_string(),
+      auth: req.headers().get(AUTHORIZATION).map(|value| {
+        let hash = Sha256::digest(value.as_bytes());
+        hash.iter().map(|b| format!("{b:02x}")).collect()
+      }),
+      accept: req
+        .headers()
+        .get(ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from),
+    }
+  }
+}
+
+/// Response kept in the cache, with the validators used to revalidate it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedResponse {
+  pub etag: Option<HeaderValue>,
+  pub last_modified: Option<HeaderValue>,
+  pub headers: HeaderMap,
+  pub body: Bytes,
+}
+
+/// Storage backend for [`CacheLayer`]
+pub trait CacheStore: Send + Sync + 'static {
+  fn get(&self, key: &CacheKey) -> Option<CachedResponse>;
+
+  fn put(&self, key: CacheKey, value: CachedResponse);
+}
+
+/// Cache store keeping all the responses in memory
+#[derive(Debug, Default)]
+pub struct MemoryCacheStore {
+  entries: Mutex<HashMap<CacheKey, CachedResponse>>,
+}
+
+impl MemoryCacheStore {
+  pub fn new() -> Self {
+    Self::default()
+  }
+}
+
+impl CacheStore for MemoryCacheStore {
+  fn get(&self, key: &CacheKey) -> Option<CachedResponse> {
+    self.entries.lock().unwrap().get(key).cloned()
+  }
+
+  fn put(&self, key: CacheKey, value: CachedResponse) {
+    self.entries.lock().unwrap().insert(key, value);
+  }
+}
+
+/// Tower layer using conditional requests to avoid downloading unchanged resources again.
+///
+/// Successful `GET` responses with an `ETag` or `Last-Modified` header are stored. Later requests for the same
+/// resource send `If-None-Match`/`If-Modified-Since`, and a `304 Not Modified` response is replaced by the stored
+/// response (including its `Link` header, so page cursors are preserved). GitHub does not count `304` responses
+/// against the rate limit.
+///
+/// Only the bodies of responses that are stored are buffered in memory; other responses are streamed from the inner
+/// service. The content of release assets downloaded with
+/// [`DownloadReleaseAssetQuery`](crate::query::download_release_asset::DownloadReleaseAssetQuery)
+/// (`Accept: application/octet-stream`) is never stored.
+///
+/// It is intended to wrap the inner service of [`HttpGithubClient`](crate::client::http::HttpGithubClient).
+#[derive(Debug)]
+pub struct CacheLayer<St = MemoryCacheStore> {
+  store: Arc<St>,
+}
+
+impl<St> Clone for CacheLayer<St> {
+  fn clone(&self) -> Self {
+    Self {
+      store: Arc::clone(&self.store),
+    }
+  }
+}
+
+impl CacheLayer {
+  pub fn new() -> Self {
+    Self::with_store(Arc::new(MemoryCacheStore::new()))
+  }
+}
+
+impl Default for CacheLayer {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl<St> CacheLayer<St> {
+  pub fn with_store(store: Arc<St>) -> Self {
+    Self { store }
+  }
+}
+
+impl<S, St> Layer<S> for CacheLayer<St> {
+  type Service = Cache<S, St>;
+
+  fn layer(&self, inner: S) -> Self::Service {
+    Cache {
+      inner,
+      store: Arc::clone(&self.store),
+    }
+  }
+}
+
+/// Service created by [`CacheLayer`]
+#[derive(Debug)]
+pub struct Cache<S, St = MemoryCacheStore> {
+  inner: S,
+  store: Arc<St>,
+}
+
+impl<S: Clone, St> Clone for Cache<S, St> {
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+      store: Arc::clone(&self.store),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+pub enum CacheError<E> {
+  #[error(transparent)]
+  Inner(E),
+  #[error("failed to receive response: {0}")]
+  Receive(String),
+}
+
+impl<S, St, TyBody> Service<Request<Full<Bytes>>> for Cache<S, St>
+where
+  S: Service<Request<Full<Bytes>>, Response = Response<TyBody>>,
+  S::Future: Send + 'static,
+  St: CacheStore,
+  TyBody: Body<Data = Bytes> + Send + 'static,
+  TyBody::Error: StdError,
+{
+  type Response = Response<ResponseBody<TyBody>>;
+  type Error = CacheError<S::Error>;
+  type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx).map_err(CacheError::Inner)
+  }
+
+  fn call(&mut self, mut req: Request<Full<Bytes>>) -> Self::Future {
+    let cacheable = req.method() == Method::GET;
+    // Asset contents are large and already identified by their digest, keep them out of the store
+    let is_binary = req
+      .headers()
+      .get(ACCEPT)
+      .is_some_and(|accept| accept.as_bytes() == b"application/octet-stream");
+    let key = CacheKey::from_request(&req);
+    let cached = if cacheable { self.store.get(&key) } else { None };
+    if let Some(cached) = cached.as_ref() {
+      let headers = req.headers_mut();
+      if let (Some(etag), false) = (&cached.etag, headers.contains_key(IF_NONE_MATCH)) {
+        headers.insert(IF_NONE_MATCH, etag.clone());
+      }
+      if let (Some(last_modified), false) = (&cached.last_modified, headers.contains_key(IF_MODIFIED_SINCE)) {
+        headers.insert(IF_MODIFIED_SINCE, last_modified.clone());
+      }
+    }
+    let res = self.inner.call(req);
+    let store = Arc::clone(&self.store);
+
+    Box::pin(async move {
+      let res = res.await.map_err(CacheError::Inner)?;
+      let (mut parts, body) = res.into_parts();
+
+      if parts.status == StatusCode::NOT_MODIFIED {
+        if let Some(cached) = cached {
+          // Keep fresh metadata from the `304` response (e.g. rate limit headers)
+          let mut headers = cached.headers;
+          for (name, value) in &parts.headers {
+            if ![CONTENT_LENGTH, CONTENT_TYPE, CONTENT_ENCODING, TRANSFER_ENCODING].contains(name) {
+              headers.insert(name.clone(), value.clone());
+            }
+          }
+          parts.status = StatusCode::OK;
+          parts.headers = headers;
+          return Ok(Response::from_parts(parts, ResponseBody::buffered(cached.body)));
+        }
+      }
+
+      let etag = parts.headers.get(ETAG).cloned();
+      let last_modified = parts.headers.get(LAST_MODIFIED).cloned();
+      let store_response =
+        cacheable && !is_binary && parts.status == StatusCode::OK && (etag.is_some() || last_modified.is_some());
+      if !store_response {
+        return Ok(Response::from_parts(parts, ResponseBody::inner(body)));
+      }
+
+      let body = body
+        .collect()
+        .await
+        .map_err(|e| CacheError::Receive(format!("{e:?}")))?
+        .to_bytes();
+      store.put(
+        key,
+        CachedResponse {
+          etag,
+          last_modified,
+          headers: parts.headers.clone(),
+          body: body.clone(),
+        },
+      );
+      Ok(Response::from_parts(parts, ResponseBody::buffered(body)))
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::client::http::HttpGithubClient;
+  use crate::common::project::{ProjectSlug, RepositoryRef};
+  use crate::context::{Context as GithubContext, GithubUrl};
+  use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+  use crate::{GithubClient, UserAgent};
+  use compact_str::CompactString;
+  use std::collections::VecDeque;
+  use std::convert::Infallible;
+  use std::future::{ready, Ready};
+  use url::Url;
+
+  /// Inner service replying with a predefined list of responses and recording requests
+  #[derive(Clone, Default)]
+  struct Script {
+    responses: Arc<Mutex<VecDeque<Response<Full<Bytes>>>>>,
+    requests: Arc<Mutex<Vec<HeaderMap>>>,
+  }
+
+  impl Service<Request<Full<Bytes>>> for Script {
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+      self.requests.lock().unwrap().push(req.headers().clone());
+      ready(Ok(
+        self.responses.lock().unwrap().pop_front().expect("unexpected request"),
+      ))
+    }
+  }
+
+  #[tokio::test]
+  async fn serve_cached_page_on_not_modified() {
+    let next = "https://api.github.com/repositories/1/releases?page=2";
+    let script = Script::default();
+    script.responses.lock().unwrap().extend([
+      Response::builder()
+        .header(ETAG, "\"abc\"")
+        .header("link", format!("<{next}>; rel=\"next\""))
+        .header("x-ratelimit-remaining", "59")
+        .body(Full::new(Bytes::from_static(b"[]")))
+        .unwrap(),
+      Response::builder()
+        .status(StatusCode::NOT_MODIFIED)
+        .header(ETAG, "\"abc\"")
+        .header("x-ratelimit-remaining", "58")
+        .body(Full::new(Bytes::new()))
+        .unwrap(),
+    ]);
+    let mut client = HttpGithubClient::new(CacheLayer::new().layer(script.clone()));
+    let context = GithubContext::new()
+      .set_github_url(GithubUrl(Url::parse("https://api.github.com/").unwrap()))
+      .set_user_agent(UserAgent::from_static("katal_github_client_test/0.0.0"));
+    let query = GetProjectReleaseListQuery::new(RepositoryRef::Slug(ProjectSlug::new(
+      CompactString::new("rust-lang"),
+      CompactString::new("rust"),
+    )))
+    .set_context(context);
+
+    let first = (&mut client).get_project_release_list(&query).await.unwrap();
+    let second = (&mut client).get_project_release_list(&query).await.unwrap();
+
+    assert_eq!(second.next.as_deref(), Some(next));
+    assert_eq!(second.items, first.items);
+    let requests = script.requests.lock().unwrap();
+    assert_eq!(requests[0].get(IF_NONE_MATCH), None);
+    assert_eq!(requests[1].get(IF_NONE_MATCH).unwrap(), "\"abc\"");
+  }
+
+  #[tokio::test]
+  async fn do_not_store_asset_content() {
+    let script = Script::default();
+    let content = || {
+      Response::builder()
+        .header(ETAG, "\"abc\"")
+        .body(Full::new(Bytes::from_static(b"asset content")))
+        .unwrap()
+    };
+    script.responses.lock().unwrap().extend([content(), content()]);
+    let mut cache = CacheLayer::new().layer(script.clone());
+    let request = || {
+      Request::builder()
+        .uri("https://api.github.com/repos/octocat/Hello-World/releases/assets/1")
+        .header(ACCEPT, "application/octet-stream")
+        .body(Full::new(Bytes::new()))
+        .unwrap()
+    };
+
+    for _ in 0..2 {
+      let res = cache.call(request()).await.unwrap();
+      assert_eq!(res.into_body().collect().await.unwrap().to_bytes(), "asset content");
+    }
+    let requests = script.requests.lock().unwrap();
+    assert_eq!(requests[1].get(IF_NONE_MATCH), None);
+  }
+
+  /// Inner service replying with a body failing on the first read, to check that the cache does not read it
+  struct Failing;
+
+  struct FailingBody;
+
+  impl Body for FailingBody {
+    type Data = Bytes;
+    type Error = std::io::Error;
+
+    fn poll_frame(
+      self: core::pin::Pin<&mut Self>,
+      _cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<http_body::Frame<Bytes>, Self::Error>>> {
+      Poll::Ready(Some(Err(std::io::Error::other("connection reset"))))
+    }
+  }
+
+  impl Service<Request<Full<Bytes>>> for Failing {
+    type Response = Response<FailingBody>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: Request<Full<Bytes>>) -> Self::Future {
+      ready(Ok(
+        Response::builder().header(ETAG, "\"abc\"").body(FailingBody).unwrap(),
+      ))
+    }
+  }
+
+  #[tokio::test]
+  async fn stream_responses_that_are_not_stored() {
+    let mut cache = CacheLayer::new().layer(Failing);
+    let request = Request::builder()
+      .uri("https://api.github.com/repos/octocat/Hello-World/releases/assets/1")
+      .header(ACCEPT, "application/octet-stream")
+      .body(Full::new(Bytes::new()))
+      .unwrap();
+
+    let res = cache.call(request).await.unwrap();
+    assert!(res.into_body().collect().await.is_err());
+  }
+}
@@ -1,68 +1,951 @@
-use crate::common::project::RepositoryRef;
-use crate::common::release::Release;
-use crate::common::Page;
+use crate::common::app::InstallationAccessToken;
+use crate::common::error::{GithubErrorBody, GithubValidationError};
+use crate::common::project::{RepositoryRef, RepositoryRefView};
+use crate::common::release::{Release, ReleaseAsset, ReleaseAssetDownload, ReleaseNotes};
+use crate::common::{Page, RateLimit};
 use crate::context::{GetRef, GithubUrl};
+use crate::query::create_installation_access_token::CreateInstallationAccessTokenQuery;
+use crate::query::create_release::CreateReleaseQuery;
+use crate::query::delete_release::DeleteReleaseQuery;
+use crate::query::delete_release_asset::DeleteReleaseAssetQuery;
+use crate::query::download_release_asset::DownloadReleaseAssetQuery;
+use crate::query::generate_release_notes::GenerateReleaseNotesQuery;
+use crate::query::get_project_latest_release::GetProjectLatestReleaseQuery;
+use crate::query::get_project_release::GetProjectReleaseQuery;
+use crate::query::get_project_release_by_tag::GetProjectReleaseByTagQuery;
 use crate::query::get_project_release_list::GetProjectReleaseListQuery;
 use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
+use crate::query::get_release_asset::GetReleaseAssetQuery;
+use crate::query::get_release_asset_list::GetReleaseAssetListQuery;
+use crate::query::get_release_asset_list_page::GetReleaseAssetListPageQuery;
+use crate::query::update_release::UpdateReleaseQuery;
+use crate::query::update_release_asset::UpdateReleaseAssetQuery;
+use crate::query::upload_release_asset::UploadReleaseAssetQuery;
 use crate::url_util::UrlExt;
 use crate::{GithubAuth, GithubAuthView};
+use bytes::Buf;
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use compact_str::CompactString;
 use core::task::{Context, Poll};
 use demurgos_headers::link::{Link, RelationType};
 use demurgos_headers::UserAgent;
-use futures::future::BoxFuture;
-use http::header::CONTENT_TYPE;
-use http::{HeaderMap, Method, Request, Response};
+use futures::future::{self, BoxFuture};
+use futures::TryStreamExt;
+use http::header::{ACCEPT, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE, LOCATION, RANGE, RETRY_AFTER};
+use http::{HeaderMap, HeaderValue, Method, Request, Response, StatusCode};
 use http_body::Body;
 use http_body_util::{BodyExt, Full};
+use serde::de::DeserializeOwned;
 use std::error::Error as StdError;
+use std::str::FromStr;
+use std::time::Duration;
 use tower_service::Service;
-use url::Url;
+use url::{Origin, Url};
 
 pub struct HttpGithubClient<TyInner> {
   inner: TyInner,
+  trusted_origins: Vec<Origin>,
 }
 
 impl<TyInner> HttpGithubClient<TyInner> {
   pub fn new(inner: TyInner) -> Self {
-    Self { inner }
+    Self {
+      inner,
+      trusted_origins: Vec::new(),
+    }
+  }
+
+  /// Trust an additional origin for URLs received from the API, such as page cursors.
+  ///
+  /// By default, only the origin of the `GithubUrl` from the query context is trusted.
+  pub fn with_trusted_origin(mut self, url: &Url) -> Self {
+    self.trusted_origins.push(url.origin());
+    self
+  }
+
+  /// Check that `url` is on a trusted origin, so credentials can be sent to it.
+  fn check_origin(&self, base: &GithubUrl, url: &str) -> Result<Url, HttpGithubClientError> {
+    let parsed = Url::parse(url).map_err(|_| HttpGithubClientError::UntrustedOrigin(url.to_string()))?;
+    self.check_url_origin(base, parsed)
+  }
+
+  fn check_url_origin(&self, base: &GithubUrl, url: Url) -> Result<Url, HttpGithubClientError> {
+    let origin = url.origin();
+    if is_api_origin(base, &origin) || self.trusted_origins.contains(&origin) {
+      Ok(url)
+    } else {
+      Err(HttpGithubClientError::UntrustedOrigin(url.into()))
+    }
+  }
+}
+
+/// Whether `origin` serves the API at `base`
+pub(crate) fn is_api_origin(base: &GithubUrl, origin: &Origin) -> bool {
+  // Uploads to github.com go through a separate host
+  let is_github_uploads =
+    base.origin() == github_origin("api.github.com") && *origin == github_origin("uploads.github.com");
+  *origin == base.origin() || is_github_uploads
+}
+
+fn github_origin(host: &str) -> Origin {
+  Origin::Tuple(String::from("https"), url::Host::Domain(String::from(host)), 443)
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+pub enum HttpGithubClientError {
+  #[error("failed to poll ready status: {0}")]
+  PollReady(String),
+  #[error("failed to send request: {0}")]
+  Send(String),
+  #[error("failed to receive response: {0}")]
+  Receive(String),
+  #[error("failed to parse response: {0}")]
+  ResponseFormat(String, Bytes),
+  #[error("operation is forbidden for provided auth")]
+  Forbidden(Option<GithubErrorBody>),
+  #[error("resource already exists")]
+  Conflict(Option<GithubErrorBody>),
+  #[error("resource not found")]
+  NotFound(Option<GithubErrorBody>),
+  #[error("rate limit exceeded")]
+  RateLimited {
+    /// Time when the primary rate limit resets, from `x-ratelimit-reset`
+    reset_at: Option<DateTime<Utc>>,
+    /// Delay to wait before retrying, from `retry-after`
+    retry_after: Option<Duration>,
+    /// Rate limit bucket that was exhausted, from `x-ratelimit-resource`
+    resource: Option<CompactString>,
+  },
+  #[error("validation failed: {0}")]
+  Validation(GithubErrorBody),
+  #[error("invalid auth: {0}")]
+  InvalidAuth(String),
+  #[error("too many redirects")]
+  TooManyRedirects,
+  #[error("refusing to send request to untrusted origin: {0}")]
+  UntrustedOrigin(String),
+  #[error("unexpected response status {0}")]
+  Status(u16, Option<GithubErrorBody>),
+  #[error("unexpected error: {0}")]
+  Other(String),
+}
+
+impl<'req, Cx, TyInner, TyBody, Str> Service<&'req GetProjectReleaseListQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+  Str: AsRef<str>,
+{
+  type Response = Page<Release>;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GetProjectReleaseListQuery<Cx, Str>) -> Self::Future {
+    let mut url: Url = repository_url(
+      GetRef::<GithubUrl>::get_ref(&req.context),
+      req.repository.as_view(),
+      ["releases"],
+    );
+
+    if let Some(per_page) = req.pagination.and_then(|pagination| pagination.per_page) {
+      url
+        .query_pairs_mut()
+        .append_pair("per_page", per_page.to_string().as_str());
+    }
+
+    let req = match Request::builder()
+      .method(Method::GET)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::new()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (headers, body) = receive(res).await?;
+      let cursors = get_cursors(&headers);
+      let body: Vec<Release> = parse_json(body)?;
+      Ok(Page {
+        first: cursors.first,
+        next: cursors.next,
+        last: cursors.last,
+        rate_limit: get_rate_limit(&headers),
+        items: body,
+      })
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req GetProjectReleaseListPageQuery<Cx, Str>>
+  for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = Page<Release>;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GetProjectReleaseListPageQuery<Cx, Str>) -> Self::Future {
+    let url: Url = match self.check_origin(GetRef::<GithubUrl>::get_ref(&req.context), req.cursor.as_ref()) {
+      Ok(url) => url,
+      Err(e) => return Box::pin(future::ready(Err(e))),
+    };
+
+    let req = match Request::builder()
+      .method(Method::GET)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::new()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (headers, body) = receive(res).await?;
+      let cursors = get_cursors(&headers);
+      let body: Vec<Release> = parse_json(body)?;
+      Ok(Page {
+        first: cursors.first,
+        next: cursors.next,
+        last: cursors.last,
+        rate_limit: get_rate_limit(&headers),
+        items: body,
+      })
+    })
+  }
+}
+
+impl<'req, Cx, TyInner, TyBody, Str> Service<&'req GetReleaseAssetListQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+  Str: AsRef<str>,
+{
+  type Response = Page<ReleaseAsset>;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GetReleaseAssetListQuery<Cx, Str>) -> Self::Future {
+    let mut url: Url = req.release.with_str(|release| {
+      repository_url(
+        GetRef::<GithubUrl>::get_ref(&req.context),
+        req.repository.as_view(),
+        ["releases", release, "assets"],
+      )
+    });
+
+    if let Some(per_page) = req.pagination.and_then(|pagination| pagination.per_page) {
+      url
+        .query_pairs_mut()
+        .append_pair("per_page", per_page.to_string().as_str());
+    }
+
+    let req = match Request::builder()
+      .method(Method::GET)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::new()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (headers, body) = receive(res).await?;
+      let cursors = get_cursors(&headers);
+      let body: Vec<ReleaseAsset> = parse_json(body)?;
+      Ok(Page {
+        first: cursors.first,
+        next: cursors.next,
+        last: cursors.last,
+        rate_limit: get_rate_limit(&headers),
+        items: body,
+      })
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req GetReleaseAssetListPageQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = Page<ReleaseAsset>;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GetReleaseAssetListPageQuery<Cx, Str>) -> Self::Future {
+    let url: Url = match self.check_origin(GetRef::<GithubUrl>::get_ref(&req.context), req.cursor.as_ref()) {
+      Ok(url) => url,
+      Err(e) => return Box::pin(future::ready(Err(e))),
+    };
+
+    let req = match Request::builder()
+      .method(Method::GET)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::new()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (headers, body) = receive(res).await?;
+      let cursors = get_cursors(&headers);
+      let body: Vec<ReleaseAsset> = parse_json(body)?;
+      Ok(Page {
+        first: cursors.first,
+        next: cursors.next,
+        last: cursors.last,
+        rate_limit: get_rate_limit(&headers),
+        items: body,
+      })
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req CreateInstallationAccessTokenQuery<Cx, Str>>
+  for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = InstallationAccessToken;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req CreateInstallationAccessTokenQuery<Cx, Str>) -> Self::Future {
+    let url: Url = {
+      let base = GetRef::<GithubUrl>::get_ref(&req.context);
+      req
+        .installation
+        .with_str(|installation_id| base.url_join(["app", "installations", installation_id, "access_tokens"]))
+    };
+
+    let req = match Request::builder()
+      .method(Method::POST)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::new()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (_, body) = receive(res).await?;
+      parse_json(body)
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req GetProjectReleaseQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GetProjectReleaseQuery<Cx, Str>) -> Self::Future {
+    let url: Url = req.release.with_str(|release| {
+      repository_url(
+        GetRef::<GithubUrl>::get_ref(&req.context),
+        req.repository.as_view(),
+        ["releases", release],
+      )
+    });
+
+    let req = match Request::builder()
+      .method(Method::GET)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::new()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (_, body) = receive(res).await?;
+      parse_json(body)
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req GetProjectReleaseByTagQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GetProjectReleaseByTagQuery<Cx, Str>) -> Self::Future {
+    let url: Url = repository_url(
+      GetRef::<GithubUrl>::get_ref(&req.context),
+      req.repository.as_view(),
+      ["releases", "tags", req.tag.as_ref()],
+    );
+
+    let req = match Request::builder()
+      .method(Method::GET)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::new()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (_, body) = receive(res).await?;
+      parse_json(body)
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req GetProjectLatestReleaseQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GetProjectLatestReleaseQuery<Cx, Str>) -> Self::Future {
+    let url: Url = repository_url(
+      GetRef::<GithubUrl>::get_ref(&req.context),
+      req.repository.as_view(),
+      ["releases", "latest"],
+    );
+
+    let req = match Request::builder()
+      .method(Method::GET)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::new()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (_, body) = receive(res).await?;
+      parse_json(body)
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req CreateReleaseQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req CreateReleaseQuery<Cx, Str>) -> Self::Future {
+    let url: Url = repository_url(
+      GetRef::<GithubUrl>::get_ref(&req.context),
+      req.repository.as_view(),
+      ["releases"],
+    );
+
+    let body = serde_json::to_vec(&req.input.as_view()).expect("request body is serializable");
+
+    let req = match Request::builder()
+      .method(Method::POST)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .header(CONTENT_TYPE, "application/json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::from(body)))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (_, body) = receive(res).await?;
+      parse_json(body)
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req UpdateReleaseQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req UpdateReleaseQuery<Cx, Str>) -> Self::Future {
+    let url: Url = req.release.with_str(|release| {
+      repository_url(
+        GetRef::<GithubUrl>::get_ref(&req.context),
+        req.repository.as_view(),
+        ["releases", release],
+      )
+    });
+
+    let body = serde_json::to_vec(&req.input.as_view()).expect("request body is serializable");
+
+    let req = match Request::builder()
+      .method(Method::PATCH)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .header(CONTENT_TYPE, "application/json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::from(body)))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (_, body) = receive(res).await?;
+      parse_json(body)
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req DeleteReleaseQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = ();
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req DeleteReleaseQuery<Cx, Str>) -> Self::Future {
+    let url: Url = req.release.with_str(|release| {
+      repository_url(
+        GetRef::<GithubUrl>::get_ref(&req.context),
+        req.repository.as_view(),
+        ["releases", release],
+      )
+    });
+
+    let req = match Request::builder()
+      .method(Method::DELETE)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::new()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      receive(res).await?;
+      Ok(())
+    })
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req UploadReleaseAssetQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = ReleaseAsset;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req UploadReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let url: Result<Url, HttpGithubClientError> = req
+      .upload_url
+      .expand(&[
+        ("name", Some(req.name.as_ref())),
+        ("label", req.label.as_ref().map(|label| label.as_ref())),
+      ])
+      .map_err(|e| HttpGithubClientError::Other(format!("{e:?}")))
+      .and_then(|url| self.check_url_origin(GetRef::<GithubUrl>::get_ref(&req.context), url));
+    let content_type = HeaderValue::from_str(req.content_type.as_ref())
+      .map_err(|e| HttpGithubClientError::Other(format!("invalid content type: {e:?}")));
+    let (url, content_type) = match url.and_then(|url| Ok((url, content_type?))) {
+      Ok(checked) => checked,
+      Err(e) => return Box::pin(future::ready(Err(e))),
+    };
+
+    let req = match Request::builder()
+      .method(Method::POST)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .header(CONTENT_TYPE, content_type)
+      .header(CONTENT_LENGTH, req.content.len())
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(req.content.clone()))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (_, body) = receive(res).await?;
+      parse_json(body)
+    })
+  }
+}
+
+/// Maximum number of redirects followed when downloading an asset
+const MAX_REDIRECTS: usize = 5;
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req DownloadReleaseAssetQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + Clone + Send + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send + 'static,
+  TyBody::Data: Send,
+  TyBody::Error: StdError + Send + Sync + 'static,
+{
+  type Response = ReleaseAssetDownload;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req DownloadReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let url: Url = req.asset.with_str(|asset| {
+      repository_url(
+        GetRef::<GithubUrl>::get_ref(&req.context),
+        req.repository.as_view(),
+        ["releases", "assets", asset],
+      )
+    });
+    let user_agent = GetRef::<UserAgent>::get_ref(&req.context).clone();
+    let range = req
+      .offset
+      .filter(|offset| *offset > 0)
+      .map(|offset| format!("bytes={offset}-"));
+    let build = move |url: &Url, auth: Option<GithubAuthView<'_>>| {
+      let mut builder = Request::builder()
+        .method(Method::GET)
+        .uri(url.as_str())
+        .header(ACCEPT, "application/octet-stream")
+        .user_agent(&user_agent)
+        .github_auth(auth);
+      if let Some(range) = range.as_deref() {
+        builder = builder.header(RANGE, range);
+      }
+      builder.body(Full::new(Bytes::new())).map_err(invalid_auth)
+    };
+
+    let auth = req.auth.as_ref().map(GithubAuth::as_view);
+    // Keep the service that was polled ready for the first request, and use a clone for redirects.
+    let clone = self.inner.clone();
+    let mut inner = core::mem::replace(&mut self.inner, clone);
+    let res = match build(&url, auth) {
+      Ok(req) => inner.call(req),
+      Err(e) => return Box::pin(future::ready(Err(e))),
+    };
+    Box::pin(async move {
+      let mut res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let origin = url.origin();
+      let mut url = url;
+      for _ in 0..MAX_REDIRECTS {
+        if !res.status().is_redirection() {
+          break;
+        }
+        let location = res
+          .headers()
+          .get(LOCATION)
+          .and_then(|location| location.to_str().ok())
+          .and_then(|location| url.join(location).ok())
+          .ok_or_else(|| HttpGithubClientError::Other(String::from("redirect without a valid location")))?;
+        url = location;
+        // Other origins, such as the storage host, must not receive the GitHub credentials
+        let auth = auth.filter(|_| url.origin() == origin);
+        let request = build(&url, auth)?;
+        future::poll_fn(|cx| inner.poll_ready(cx))
+          .await
+          .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))?;
+        res = inner
+          .call(request)
+          .await
+          .map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      }
+      if res.status().is_redirection() {
+        return Err(HttpGithubClientError::TooManyRedirects);
+      }
+
+      let status = res.status();
+      if !status.is_success() {
+        return Err(read_error(res).await);
+      }
+      let (parts, body) = res.into_parts();
+      let content_range =
+        get_header::<String>(&parts.headers, CONTENT_RANGE.as_str()).and_then(|range| parse_content_range(&range));
+      let (offset, size) = match (status, content_range) {
+        (StatusCode::PARTIAL_CONTENT, Some((start, size))) => (start, size),
+        _ => (0, get_header::<u64>(&parts.headers, CONTENT_LENGTH.as_str())),
+      };
+      let content = body
+        .into_data_stream()
+        .map_ok(|mut chunk| chunk.copy_to_bytes(chunk.remaining()))
+        .map_err(std::io::Error::other);
+      Ok(ReleaseAssetDownload {
+        offset,
+        size,
+        content_type: get_header(&parts.headers, CONTENT_TYPE.as_str()),
+        content: Box::pin(content),
+      })
+    })
   }
 }
 
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
-pub enum HttpGithubClientError {
-  #[error("failed to poll ready status: {0}")]
-  PollReady(String),
-  #[error("failed to send request: {0}")]
-  Send(String),
-  #[error("failed to receive response: {0}")]
-  Receive(String),
-  #[error("failed to parse response: {0}")]
-  ResponseFormat(String, Bytes),
-  #[error("operation is forbidden for provided auth")]
-  Forbidden,
-  #[error("resource already exists")]
-  Conflict,
-  #[error("resource not found")]
-  NotFound,
-  #[error("unexpected error: {0}")]
-  Other(String),
+/// Report a request that could not be built.
+///
+/// URLs and user agents are always valid header values, so this is an auth value that is not (e.g. a token with a
+/// trailing newline).
+fn invalid_auth(e: http::Error) -> HttpGithubClientError {
+  HttpGithubClientError::InvalidAuth(format!("{e}"))
 }
 
-impl<'req, Cx, TyInner, TyBody, Str> Service<&'req GetProjectReleaseListQuery<Cx, Str>> for HttpGithubClient<TyInner>
+/// Parse a `Content-Range: bytes {start}-{end}/{size}` header into its start and size
+fn parse_content_range(value: &str) -> Option<(u64, Option<u64>)> {
+  let (range, size) = value.strip_prefix("bytes ")?.split_once('/')?;
+  let (start, _) = range.split_once('-')?;
+  Some((start.trim().parse().ok()?, size.trim().parse().ok()))
+}
+
+/// Read the error from an unsuccessful response
+async fn read_error<TyBody>(res: Response<TyBody>) -> HttpGithubClientError
+where
+  TyBody: Body,
+  TyBody::Error: StdError,
+{
+  let (parts, body) = res.into_parts();
+  match body.collect().await {
+    Ok(body) => status_error(parts.status, &parts.headers, &body.to_bytes()),
+    Err(e) => HttpGithubClientError::Receive(format!("{e:?}")),
+  }
+}
+
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req GetReleaseAssetQuery<Cx, Str>> for HttpGithubClient<TyInner>
 where
   Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
   TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
   TyInner::Error: StdError,
   TyInner::Future: Send,
   TyBody: Body + Send,
   TyBody::Data: Send,
   TyBody::Error: StdError,
-  Str: AsRef<str>,
 {
-  type Response = Page<Release>;
+  type Response = ReleaseAsset;
   type Error = HttpGithubClientError;
   type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
 
@@ -73,59 +956,92 @@ where
       .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
   }
 
-  fn call(&mut self, req: &'req GetProjectReleaseListQuery<Cx, Str>) -> Self::Future {
-    let mut url: Url = {
-      let base = GetRef::<GithubUrl>::get_ref(&req.context);
-      match req.repository.as_view() {
-        RepositoryRef::Id(repo_id) => repo_id.with_str(|repo_id| base.url_join(["repositories", repo_id, "releases"])),
-        RepositoryRef::Slug(slug) => base.url_join(["repos", slug.owner, slug.name, "releases"]),
-      }
-    };
-
-    {
-      let mut query = url.query_pairs_mut();
-      if let Some(pagination) = req.pagination {
-        if let Some(per_page) = pagination.per_page {
-          query.append_pair("per_page", per_page.to_string().as_str());
-        }
-      }
-    }
+  fn call(&mut self, req: &'req GetReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let url: Url = req.asset.with_str(|asset| {
+      repository_url(
+        GetRef::<GithubUrl>::get_ref(&req.context),
+        req.repository.as_view(),
+        ["releases", "assets", asset],
+      )
+    });
 
-    let req = Request::builder()
+    let req = match Request::builder()
       .method(Method::GET)
       .uri(url.as_str())
-      .header(CONTENT_TYPE, "application/vnd.github+json")
+      .header(ACCEPT, "application/vnd.github+json")
       .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
       .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
       .body(Full::new(Bytes::new()))
-      .unwrap();
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
     let res = self.inner.call(req);
     Box::pin(async move {
       let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
-      let cursors = get_cursors(res.headers());
-      let body = res
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| HttpGithubClientError::Receive(format!("{e:?}")))?;
-      let body: Bytes = body.to_bytes();
+      let (_, body) = receive(res).await?;
+      parse_json(body)
+    })
+  }
+}
 
-      let body: Vec<Release> =
-        serde_json::from_slice(&body).map_err(|e| HttpGithubClientError::ResponseFormat(format!("{e:?}"), body))?;
-      Ok(Page {
-        first: cursors.first,
-        next: cursors.next,
-        last: cursors.last,
-        items: body,
-      })
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req UpdateReleaseAssetQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = ReleaseAsset;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req UpdateReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let url: Url = req.asset.with_str(|asset| {
+      repository_url(
+        GetRef::<GithubUrl>::get_ref(&req.context),
+        req.repository.as_view(),
+        ["releases", "assets", asset],
+      )
+    });
+
+    let body = serde_json::to_vec(&req.input.as_view()).expect("request body is serializable");
+
+    let req = match Request::builder()
+      .method(Method::PATCH)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .header(CONTENT_TYPE, "application/json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::from(body)))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (_, body) = receive(res).await?;
+      parse_json(body)
     })
   }
 }
 
-impl<'req, Cx, Str, TyInner, TyBody> Service<&'req GetProjectReleaseListPageQuery<Cx, Str>>
-  for HttpGithubClient<TyInner>
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req DeleteReleaseAssetQuery<Cx, Str>> for HttpGithubClient<TyInner>
 where
-  Cx: GetRef<UserAgent>,
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
   Str: AsRef<str>,
   TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
   TyInner::Error: StdError,
@@ -134,7 +1050,7 @@ where
   TyBody::Data: Send,
   TyBody::Error: StdError,
 {
-  type Response = Page<Release>;
+  type Response = ();
   type Error = HttpGithubClientError;
   type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
 
@@ -145,40 +1061,180 @@ where
       .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
   }
 
-  fn call(&mut self, req: &'req GetProjectReleaseListPageQuery<Cx, Str>) -> Self::Future {
-    let url: &str = req.cursor.as_ref();
+  fn call(&mut self, req: &'req DeleteReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let url: Url = req.asset.with_str(|asset| {
+      repository_url(
+        GetRef::<GithubUrl>::get_ref(&req.context),
+        req.repository.as_view(),
+        ["releases", "assets", asset],
+      )
+    });
 
-    let req = Request::builder()
-      .method(Method::GET)
-      .uri(url)
+    let req = match Request::builder()
+      .method(Method::DELETE)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
       .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
       .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
       .body(Full::new(Bytes::new()))
-      .unwrap();
-
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
     let res = self.inner.call(req);
     Box::pin(async move {
       let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
-      let cursors = get_cursors(res.headers());
-      let body = res
-        .into_body()
-        .collect()
-        .await
-        .map_err(|e| HttpGithubClientError::Receive(format!("{e:?}")))?;
-      let body: Bytes = body.to_bytes();
+      receive(res).await?;
+      Ok(())
+    })
+  }
+}
 
-      let body: Vec<Release> =
-        serde_json::from_slice(&body).map_err(|e| HttpGithubClientError::ResponseFormat(format!("{e:?}"), body))?;
-      Ok(Page {
-        first: cursors.first,
-        next: cursors.next,
-        last: cursors.last,
-        items: body,
-      })
+impl<'req, Cx, Str, TyInner, TyBody> Service<&'req GenerateReleaseNotesQuery<Cx, Str>> for HttpGithubClient<TyInner>
+where
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent>,
+  Str: AsRef<str>,
+  TyInner: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + 'req,
+  TyInner::Error: StdError,
+  TyInner::Future: Send,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = ReleaseNotes;
+  type Error = HttpGithubClientError;
+  type Future = BoxFuture<'req, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self
+      .inner
+      .poll_ready(cx)
+      .map_err(|e| HttpGithubClientError::PollReady(format!("{e:?}")))
+  }
+
+  fn call(&mut self, req: &'req GenerateReleaseNotesQuery<Cx, Str>) -> Self::Future {
+    let url: Url = repository_url(
+      GetRef::<GithubUrl>::get_ref(&req.context),
+      req.repository.as_view(),
+      ["releases", "generate-notes"],
+    );
+
+    let body = serde_json::to_vec(&req.input.as_view()).expect("request body is serializable");
+
+    let req = match Request::builder()
+      .method(Method::POST)
+      .uri(url.as_str())
+      .header(ACCEPT, "application/vnd.github+json")
+      .header(CONTENT_TYPE, "application/json")
+      .user_agent(GetRef::<UserAgent>::get_ref(&req.context))
+      .github_auth(req.auth.as_ref().map(GithubAuth::as_view))
+      .body(Full::new(Bytes::from(body)))
+    {
+      Ok(req) => req,
+      Err(e) => return Box::pin(future::ready(Err(invalid_auth(e)))),
+    };
+    let res = self.inner.call(req);
+    Box::pin(async move {
+      let res: Response<TyBody> = res.await.map_err(|e| HttpGithubClientError::Send(format!("{e:?}")))?;
+      let (_, body) = receive(res).await?;
+      parse_json(body)
     })
   }
 }
 
+/// Receive the full response body, failing if the response status is not a success.
+async fn receive<TyBody>(res: Response<TyBody>) -> Result<(HeaderMap, Bytes), HttpGithubClientError>
+where
+  TyBody: Body,
+  TyBody::Error: StdError,
+{
+  let (parts, body) = res.into_parts();
+  let body = body
+    .collect()
+    .await
+    .map_err(|e| HttpGithubClientError::Receive(format!("{e:?}")))?;
+  let body: Bytes = body.to_bytes();
+  if !parts.status.is_success() {
+    return Err(status_error(parts.status, &parts.headers, &body));
+  }
+  Ok((parts.headers, body))
+}
+
+fn status_error(status: StatusCode, headers: &HeaderMap, body: &[u8]) -> HttpGithubClientError {
+  let body: Option<GithubErrorBody> = serde_json::from_slice(body).ok();
+  if is_rate_limited(status, headers, body.as_ref()) {
+    return HttpGithubClientError::RateLimited {
+      reset_at: get_header::<i64>(headers, "x-ratelimit-reset").and_then(|reset| DateTime::from_timestamp(reset, 0)),
+      retry_after: get_header::<u64>(headers, "retry-after").map(Duration::from_secs),
+      resource: get_header::<CompactString>(headers, "x-ratelimit-resource"),
+    };
+  }
+  match status {
+    StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => HttpGithubClientError::Forbidden(body),
+    StatusCode::NOT_FOUND | StatusCode::GONE => HttpGithubClientError::NotFound(body),
+    StatusCode::CONFLICT => HttpGithubClientError::Conflict(body),
+    StatusCode::UNPROCESSABLE_ENTITY => match body {
+      Some(body)
+        if body
+          .errors
+          .iter()
+          .any(|error| error.code == GithubValidationError::ALREADY_EXISTS) =>
+      {
+        HttpGithubClientError::Conflict(Some(body))
+      }
+      Some(body) => HttpGithubClientError::Validation(body),
+      None => HttpGithubClientError::Status(status.as_u16(), None),
+    },
+    _ => HttpGithubClientError::Status(status.as_u16(), body),
+  }
+}
+
+/// Detect primary and secondary rate limit errors
+///
+/// See <https://docs.github.com/en/rest/using-the-rest-api/rate-limits-for-the-rest-api?apiVersion=2022-11-28#exceeding-the-rate-limit>
+pub(crate) fn is_rate_limited(status: StatusCode, headers: &HeaderMap, body: Option<&GithubErrorBody>) -> bool {
+  match status {
+    StatusCode::TOO_MANY_REQUESTS => true,
+    StatusCode::FORBIDDEN => {
+      get_header::<u64>(headers, "x-ratelimit-remaining") == Some(0)
+        || headers.contains_key(RETRY_AFTER)
+        || body.is_some_and(|body| body.message.to_ascii_lowercase().contains("rate limit"))
+    }
+    _ => false,
+  }
+}
+
+fn get_rate_limit(headers: &HeaderMap) -> Option<RateLimit> {
+  Some(RateLimit {
+    limit: get_header(headers, "x-ratelimit-limit")?,
+    remaining: get_header(headers, "x-ratelimit-remaining")?,
+    used: get_header(headers, "x-ratelimit-used")?,
+    reset: DateTime::from_timestamp(get_header(headers, "x-ratelimit-reset")?, 0)?,
+    resource: get_header(headers, "x-ratelimit-resource"),
+  })
+}
+
+/// URL of a resource of the repository, e.g. `/repos/{owner}/{repo}/releases`
+fn repository_url<'s, I>(base: &Url, repository: RepositoryRefView<'_>, segments: I) -> Url
+where
+  I: IntoIterator<Item = &'s str>,
+{
+  let url = match repository {
+    RepositoryRef::Id(repo_id) => repo_id.with_str(|repo_id| base.url_join(["repositories", repo_id])),
+    RepositoryRef::Slug(slug) => base.url_join(["repos", slug.owner, slug.name]),
+  };
+  url.url_join(segments)
+}
+
+/// Read and parse a header with a textual value
+pub(crate) fn get_header<T: FromStr>(headers: &HeaderMap, name: &str) -> Option<T> {
+  headers.get(name)?.to_str().ok()?.trim().parse().ok()
+}
+
+fn parse_json<T: DeserializeOwned>(body: Bytes) -> Result<T, HttpGithubClientError> {
+  serde_json::from_slice(&body).map_err(|e| HttpGithubClientError::ResponseFormat(format!("{e:?}"), body))
+}
+
 struct Cursors<Str> {
   first: Option<Str>,
   next: Option<Str>,
@@ -237,9 +1293,541 @@ impl RequestBuilderExt for http::request::Builder {
   fn github_auth(self, github_auth: Option<GithubAuthView<'_>>) -> Self {
     if let Some(auth) = github_auth {
       let (key, value) = auth.http_header();
-      self.header(key, value)
+      // Invalid values (e.g. a token with a trailing newline) are reported when the request is built
+      let mut builder = self.header(key, value);
+      if let Some(value) = builder.headers_mut().and_then(|headers| headers.get_mut(key)) {
+        value.set_sensitive(true);
+      }
+      builder
     } else {
       self
     }
   }
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::common::release::{ReleaseAssetId, ReleaseId};
+  use crate::url_util::UriTemplate;
+  use std::collections::VecDeque;
+  use std::sync::{Arc, Mutex};
+
+  #[test]
+  fn send_github_auth_header() {
+    let request = |auth: GithubAuthView<'_>| {
+      Request::builder()
+        .uri("https://api.github.com/")
+        .github_auth(Some(auth))
+        .body(())
+    };
+    for (auth, expected) in [
+      (GithubAuth::Bearer("ghp_secret"), "Bearer ghp_secret"),
+      (GithubAuth::Token("ghp_secret"), "token ghp_secret"),
+      (
+        GithubAuth::Basic {
+          client_id: "Iv1.abc",
+          client_secret: "secret",
+        },
+        "Basic SXYxLmFiYzpzZWNyZXQ=",
+      ),
+    ] {
+      let request = request(auth).unwrap();
+      let value = &request.headers()[http::header::AUTHORIZATION];
+      assert_eq!(value, expected);
+      assert!(value.is_sensitive());
+    }
+    assert!(request(GithubAuth::Bearer("ghp_secret\n")).is_err());
+  }
+
+  #[test]
+  fn read_validation_error() {
+    let raw = br#"{"message":"Validation Failed","errors":[{"resource":"Release","code":"invalid","field":"tag_name"}],"documentation_url":"https://docs.github.com/rest/releases/releases#create-a-release","status":"422"}"#;
+    let actual = status_error(StatusCode::UNPROCESSABLE_ENTITY, &HeaderMap::new(), raw);
+    let expected = HttpGithubClientError::Validation(GithubErrorBody {
+      message: CompactString::new("Validation Failed"),
+      documentation_url: Some(CompactString::new(
+        "https://docs.github.com/rest/releases/releases#create-a-release",
+      )),
+      status: Some(CompactString::new("422")),
+      errors: vec![GithubValidationError {
+        resource: Some(CompactString::new("Release")),
+        field: Some(CompactString::new("tag_name")),
+        code: CompactString::new(GithubValidationError::INVALID),
+        message: None,
+      }],
+    });
+    assert_eq!(actual, expected);
+    assert_eq!(
+      actual.to_string(),
+      "validation failed: Validation Failed; Release.tag_name: invalid"
+    );
+  }
+
+  #[test]
+  fn read_already_exists_as_conflict() {
+    let raw = br#"{"message":"Validation Failed","errors":[{"resource":"Release","code":"already_exists","field":"tag_name"}],"documentation_url":"https://docs.github.com/rest/releases/releases#create-a-release","status":"422"}"#;
+    let actual = status_error(StatusCode::UNPROCESSABLE_ENTITY, &HeaderMap::new(), raw);
+    assert!(matches!(
+      actual,
+      HttpGithubClientError::Conflict(Some(body)) if body.errors[0].field.as_deref() == Some("tag_name")
+    ));
+  }
+
+  #[test]
+  fn read_not_found_without_body() {
+    let actual = status_error(StatusCode::NOT_FOUND, &HeaderMap::new(), b"<html></html>");
+    assert_eq!(actual, HttpGithubClientError::NotFound(None));
+  }
+
+  /// Inner service failing the test if any request is sent
+  struct NoRequest;
+
+  impl Service<Request<Full<Bytes>>> for NoRequest {
+    type Response = Response<Full<Bytes>>;
+    type Error = std::convert::Infallible;
+    type Future = future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+      panic!("unexpected request to {}", req.uri())
+    }
+  }
+
+  #[tokio::test]
+  async fn reject_cursor_on_untrusted_origin() {
+    let mut client = HttpGithubClient::new(NoRequest);
+    let context = context();
+    let mut query =
+      GetProjectReleaseListPageQuery::new("https://api.github.com.evil.example/repositories/1/releases?page=2")
+        .set_context(context);
+    query.auth = Some(GithubAuth::Bearer("secret"));
+    let actual = client.call(&query).await;
+    assert_eq!(
+      actual,
+      Err(HttpGithubClientError::UntrustedOrigin(String::from(
+        "https://api.github.com.evil.example/repositories/1/releases?page=2"
+      )))
+    );
+  }
+
+  #[tokio::test]
+  async fn reject_invalid_auth() {
+    let mut client = HttpGithubClient::new(NoRequest);
+    let context = context();
+    let mut query = GetProjectReleaseListQuery::new(RepositoryRef::Slug(crate::common::project::ProjectSlug::new(
+      "octocat", "hello",
+    )))
+    .set_context(context);
+    query.auth = Some(GithubAuth::Bearer("ghp_secret\n"));
+    let actual = client.call(&query).await;
+    assert!(matches!(actual, Err(HttpGithubClientError::InvalidAuth(_))));
+  }
+
+  #[tokio::test]
+  async fn send_credentials_to_trusted_origin() {
+    let mut client =
+      client_with([(StatusCode::OK, "[]")]).with_trusted_origin(&Url::parse("https://github-proxy.example/").unwrap());
+    let context = context();
+    let mut query = GetProjectReleaseListPageQuery::new("https://github-proxy.example/repositories/1/releases?page=2")
+      .set_context(context);
+    query.auth = Some(GithubAuth::Bearer("secret"));
+
+    let page = client.call(&query).await.unwrap();
+    assert_eq!(page.items, []);
+    let inner = &client.inner;
+    let requests = &inner.requests;
+    assert_eq!(
+      requests[0].uri(),
+      "https://github-proxy.example/repositories/1/releases?page=2"
+    );
+    assert_eq!(requests[0].headers()["authorization"], "Bearer secret");
+  }
+
+  #[test]
+  fn read_primary_rate_limit_error() {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-ratelimit-limit", HeaderValue::from_static("5000"));
+    headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+    headers.insert("x-ratelimit-used", HeaderValue::from_static("5000"));
+    headers.insert("x-ratelimit-reset", HeaderValue::from_static("1735732800"));
+    headers.insert("x-ratelimit-resource", HeaderValue::from_static("core"));
+    let raw = br#"{"message":"API rate limit exceeded for user ID 1.","documentation_url":"https://docs.github.com/rest/overview/rate-limits-for-the-rest-api","status":"403"}"#;
+    let actual = status_error(StatusCode::FORBIDDEN, &headers, raw);
+    let expected = HttpGithubClientError::RateLimited {
+      reset_at: DateTime::from_timestamp(1735732800, 0),
+      retry_after: None,
+      resource: Some(CompactString::new("core")),
+    };
+    assert_eq!(actual, expected);
+  }
+
+  const RELEASE_ASSET: &str = include_str!("../../test-resources/http/release-asset.json");
+  const RELEASE_ASSET_LIST: &str = include_str!("../../test-resources/http/release-asset-list.json");
+  const UPLOADED_RELEASE_ASSET: &str = include_str!("../../test-resources/http/uploaded-release-asset.json");
+  const RELEASE_NOTES: &str = include_str!("../../test-resources/http/release-notes.json");
+  const RELEASE_NOT_FOUND: &str = include_str!("../../test-resources/http/release-not-found.json");
+
+  fn context() -> crate::context::Context<GithubUrl, UserAgent> {
+    crate::context::Context::new()
+      .set_github_url(GithubUrl(Url::parse("https://api.github.com/").unwrap()))
+      .set_user_agent(UserAgent::from_static("katal_github_client_test/0.0.0"))
+  }
+
+  /// Client whose inner service replies to each request with the next of the `responses`
+  fn client_with(responses: impl IntoIterator<Item = (StatusCode, &'static str)>) -> HttpGithubClient<Reply> {
+    HttpGithubClient::new(Reply {
+      responses: responses.into_iter().collect(),
+      requests: Vec::new(),
+    })
+  }
+
+  /// Inner service replying to requests with scripted responses, recording the requests
+  struct Reply {
+    responses: VecDeque<(StatusCode, &'static str)>,
+    requests: Vec<Request<Full<Bytes>>>,
+  }
+
+  impl Reply {
+    fn uris(&self) -> Vec<String> {
+      self.requests.iter().map(|req| req.uri().to_string()).collect()
+    }
+  }
+
+  impl Service<Request<Full<Bytes>>> for Reply {
+    type Response = Response<Full<Bytes>>;
+    type Error = std::convert::Infallible;
+    type Future = future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+      let Some((status, body)) = self.responses.pop_front() else {
+        panic!("unexpected request to {}", req.uri())
+      };
+      self.requests.push(req);
+      let mut res = Response::new(Full::new(Bytes::from_static(body.as_bytes())));
+      *res.status_mut() = status;
+      future::ready(Ok(res))
+    }
+  }
+
+  #[tokio::test]
+  async fn get_missing_release_by_tag() {
+    let mut client = client_with([(StatusCode::NOT_FOUND, RELEASE_NOT_FOUND)]);
+    let context = context();
+    let query = GetProjectReleaseByTagQuery::new(
+      RepositoryRef::Slug(crate::common::project::ProjectSlug::new("octocat", "Hello-World")),
+      "release/v1.0",
+    )
+    .set_context(context);
+    let actual = client.call(&query).await;
+    assert!(matches!(actual, Err(HttpGithubClientError::NotFound(Some(body))) if body.message == "Not Found"));
+    assert_eq!(
+      client.inner.uris(),
+      ["https://api.github.com/repos/octocat/Hello-World/releases/tags/release%2Fv1.0"]
+    );
+  }
+
+  #[test]
+  fn write_release_input_without_unset_fields() {
+    let mut input = crate::common::release::InputRelease::new("v1.0.0");
+    input.make_latest = Some(crate::common::release::MakeLatest::Legacy);
+    input.generate_release_notes = Some(true);
+    assert_eq!(
+      serde_json::to_string(&input).unwrap(),
+      r#"{"tag_name":"v1.0.0","make_latest":"legacy","generate_release_notes":true}"#
+    );
+  }
+
+  #[tokio::test]
+  async fn generate_release_notes() {
+    let mut client = client_with([(StatusCode::OK, RELEASE_NOTES)]);
+    let context = context();
+    let mut input = crate::common::release::InputReleaseNotes::new("v1.1.0");
+    input.previous_tag_name = Some("v1.0.0");
+    let query = GenerateReleaseNotesQuery::new(
+      RepositoryRef::Slug(crate::common::project::ProjectSlug::new("octocat", "Hello-World")),
+      input,
+    )
+    .set_context(context);
+
+    let notes = client.call(&query).await.unwrap();
+    assert_eq!(notes.name, "v1.1.0");
+    assert_eq!(
+      client.inner.uris(),
+      ["https://api.github.com/repos/octocat/Hello-World/releases/generate-notes"]
+    );
+  }
+
+  #[tokio::test]
+  async fn upload_asset_to_uploads_host() {
+    let mut client = client_with([(StatusCode::CREATED, UPLOADED_RELEASE_ASSET)]);
+    let context = context();
+    let mut query = UploadReleaseAssetQuery::new(
+      UriTemplate::new("https://uploads.github.com/repos/octocat/Hello-World/releases/1/assets{?name,label}"),
+      "hello world.txt",
+      "text/plain",
+      Bytes::from_static(b"hello"),
+    )
+    .set_context(context);
+    query.auth = Some(GithubAuth::Bearer("secret"));
+
+    let asset = client.call(&query).await.unwrap();
+    assert_eq!(asset.size, 5);
+    assert_eq!(
+      client.inner.uris(),
+      ["https://uploads.github.com/repos/octocat/Hello-World/releases/1/assets?name=hello%20world.txt"]
+    );
+
+    query.upload_url = UriTemplate::new("https://uploads.evil.example/assets{?name,label}");
+    assert_eq!(
+      client.call(&query).await,
+      Err(HttpGithubClientError::UntrustedOrigin(String::from(
+        "https://uploads.evil.example/assets?name=hello%20world.txt"
+      )))
+    );
+  }
+
+  #[tokio::test]
+  async fn get_release_asset_list() {
+    let mut client = client_with([(StatusCode::OK, RELEASE_ASSET_LIST)]);
+    let context = context();
+    let mut query = GetReleaseAssetListQuery::new(
+      RepositoryRef::Slug(crate::common::project::ProjectSlug::new("octocat", "Hello-World")),
+      ReleaseId::new(10),
+    )
+    .set_context(context);
+    query.pagination = Some(crate::common::Pagination {
+      per_page: core::num::NonZeroU8::new(50),
+    });
+
+    let page = client.call(&query).await.unwrap();
+    assert_eq!(page.items.len(), 1);
+    assert_eq!(page.items[0].id, ReleaseAssetId::new(1));
+    let inner = &client.inner;
+    assert_eq!(inner.requests[0].method(), Method::GET);
+    assert_eq!(
+      inner.uris(),
+      ["https://api.github.com/repos/octocat/Hello-World/releases/10/assets?per_page=50"]
+    );
+  }
+
+  #[tokio::test]
+  async fn get_release_asset() {
+    let mut client = client_with([(StatusCode::OK, RELEASE_ASSET)]);
+    let context = context();
+    let query = GetReleaseAssetQuery::new(
+      RepositoryRef::Slug(crate::common::project::ProjectSlug::new("octocat", "Hello-World")),
+      ReleaseAssetId::new(1),
+    )
+    .set_context(context);
+
+    let asset = client.call(&query).await.unwrap();
+    assert_eq!(asset.name, "hello.txt");
+    let inner = &client.inner;
+    assert_eq!(inner.requests[0].method(), Method::GET);
+    assert_eq!(
+      inner.uris(),
+      ["https://api.github.com/repos/octocat/Hello-World/releases/assets/1"]
+    );
+  }
+
+  #[tokio::test]
+  async fn update_release_asset() {
+    let mut client = client_with([(StatusCode::OK, RELEASE_ASSET)]);
+    let context = context();
+    let input = crate::common::release::InputReleaseAssetPatch {
+      label: Some("Greeting"),
+      ..Default::default()
+    };
+    let query = UpdateReleaseAssetQuery::new(
+      RepositoryRef::Slug(crate::common::project::ProjectSlug::new("octocat", "Hello-World")),
+      ReleaseAssetId::new(1),
+      input,
+    )
+    .set_context(context);
+
+    let asset = client.call(&query).await.unwrap();
+    assert_eq!(asset.label.as_deref(), Some("Greeting"));
+    let request = client.inner.requests.remove(0);
+    assert_eq!(request.method(), Method::PATCH);
+    assert_eq!(
+      request.uri(),
+      "https://api.github.com/repos/octocat/Hello-World/releases/assets/1"
+    );
+    assert_eq!(request.headers()[CONTENT_TYPE], "application/json");
+    let body = request.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, r#"{"label":"Greeting"}"#);
+  }
+
+  #[tokio::test]
+  async fn delete_release_asset() {
+    let mut client = client_with([(StatusCode::NO_CONTENT, "")]);
+    let context = context();
+    let query = DeleteReleaseAssetQuery::new(
+      RepositoryRef::Slug(crate::common::project::ProjectSlug::new("octocat", "Hello-World")),
+      ReleaseAssetId::new(1),
+    )
+    .set_context(context);
+
+    assert_eq!(client.call(&query).await, Ok(()));
+    let inner = &client.inner;
+    assert_eq!(inner.requests[0].method(), Method::DELETE);
+    assert_eq!(
+      inner.uris(),
+      ["https://api.github.com/repos/octocat/Hello-World/releases/assets/1"]
+    );
+  }
+
+  /// Inner service redirecting asset downloads to a storage host, recording the requests
+  ///
+  /// If the repository is `renamed`, requests using its old name are first redirected to its id. If the storage host is
+  /// `looping`, it keeps redirecting to itself.
+  #[derive(Clone, Default)]
+  struct Storage {
+    renamed: bool,
+    looping: bool,
+    requests: Arc<Mutex<Vec<Request<Full<Bytes>>>>>,
+  }
+
+  impl Service<Request<Full<Bytes>>> for Storage {
+    type Response = Response<Full<Bytes>>;
+    type Error = std::convert::Infallible;
+    type Future = future::Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+      let res = if self.renamed && req.uri().path().starts_with("/repos/") {
+        Response::builder()
+          .status(StatusCode::MOVED_PERMANENTLY)
+          .header(LOCATION, "/repositories/1296269/releases/assets/5011")
+          .body(Full::new(Bytes::new()))
+      } else if req.uri().host() == Some("api.github.com") {
+        Response::builder()
+          .status(StatusCode::FOUND)
+          .header(
+            LOCATION,
+            "https://objects.githubusercontent.com/asset/5011?signature=abc",
+          )
+          .body(Full::new(Bytes::new()))
+      } else if self.looping {
+        Response::builder()
+          .status(StatusCode::FOUND)
+          .header(LOCATION, req.uri().to_string())
+          .body(Full::new(Bytes::new()))
+      } else {
+        Response::builder()
+          .status(StatusCode::PARTIAL_CONTENT)
+          .header(CONTENT_TYPE, "application/octet-stream")
+          .header(CONTENT_RANGE, "bytes 6-10/11")
+          .body(Full::new(Bytes::from_static(b"world")))
+      };
+      self.requests.lock().unwrap().push(req);
+      future::ready(Ok(res.unwrap()))
+    }
+  }
+
+  #[tokio::test]
+  async fn resume_asset_download_from_storage() {
+    let storage = Storage::default();
+    let mut client = HttpGithubClient::new(storage.clone());
+    let context = context();
+    let mut query = DownloadReleaseAssetQuery::new(
+      RepositoryRef::Slug(crate::common::project::ProjectSlug::new("octocat", "Hello-World")),
+      ReleaseAssetId::new(5011),
+    )
+    .set_context(context);
+    query.auth = Some(GithubAuth::Bearer("secret"));
+    query.offset = Some(6);
+
+    let download = client.call(&query).await.unwrap();
+    assert_eq!((download.offset, download.size), (6, Some(11)));
+    let content: Vec<Bytes> = download.content.try_collect().await.unwrap();
+    assert_eq!(content.concat(), b"world");
+
+    let requests = storage.requests.lock().unwrap();
+    assert_eq!(
+      requests[0].uri(),
+      "https://api.github.com/repos/octocat/Hello-World/releases/assets/5011"
+    );
+    assert_eq!(requests[0].headers()[ACCEPT], "application/octet-stream");
+    assert_eq!(requests[0].headers()["authorization"], "Bearer secret");
+    assert_eq!(
+      requests[1].uri(),
+      "https://objects.githubusercontent.com/asset/5011?signature=abc"
+    );
+    assert_eq!(requests[1].headers().get("authorization"), None);
+    assert_eq!(requests[1].headers()[RANGE], "bytes=6-");
+  }
+
+  #[tokio::test]
+  async fn keep_credentials_on_same_origin_redirect() {
+    let storage = Storage {
+      renamed: true,
+      ..Storage::default()
+    };
+    let mut client = HttpGithubClient::new(storage.clone());
+    let context = context();
+    let mut query = DownloadReleaseAssetQuery::new(
+      RepositoryRef::Slug(crate::common::project::ProjectSlug::new("octocat", "Hello-World")),
+      ReleaseAssetId::new(5011),
+    )
+    .set_context(context);
+    query.auth = Some(GithubAuth::Bearer("secret"));
+
+    let download = client.call(&query).await.unwrap();
+    let content: Vec<Bytes> = download.content.try_collect().await.unwrap();
+    assert_eq!(content.concat(), b"world");
+
+    let requests = storage.requests.lock().unwrap();
+    let sent: Vec<(String, Option<&HeaderValue>)> = requests
+      .iter()
+      .map(|req| (req.uri().to_string(), req.headers().get("authorization")))
+      .collect();
+    assert_eq!(
+      sent,
+      [
+        (
+          String::from("https://api.github.com/repos/octocat/Hello-World/releases/assets/5011"),
+          Some(&HeaderValue::from_static("Bearer secret"))
+        ),
+        (
+          String::from("https://api.github.com/repositories/1296269/releases/assets/5011"),
+          Some(&HeaderValue::from_static("Bearer secret"))
+        ),
+        (
+          String::from("https://objects.githubusercontent.com/asset/5011?signature=abc"),
+          None
+        ),
+      ]
+    );
+  }
+
+  #[tokio::test]
+  async fn stop_after_too_many_redirects() {
+    let storage = Storage {
+      looping: true,
+      ..Storage::default()
+    };
+    let mut client = HttpGithubClient::new(storage.clone());
+    let context = context();
+    let query = DownloadReleaseAssetQuery::new(
+      RepositoryRef::Slug(crate::common::project::ProjectSlug::new("octocat", "Hello-World")),
+      ReleaseAssetId::new(5011),
+    )
+    .set_context(context);
+
+    let actual = client.call(&query).await.map(|_| ());
+    assert_eq!(actual, Err(HttpGithubClientError::TooManyRedirects));
+    assert_eq!(storage.requests.lock().unwrap().len(), 1 + MAX_REDIRECTS);
+  }
+}
@@ -0,0 +1,220 @@
+use crate::app::{GithubAppError, InstallationAuth};
+use crate::client::http::{is_api_origin, HttpGithubClient};
+use crate::context::{GetRef, GithubUrl};
+use crate::UserAgent;
+use bytes::Bytes;
+use core::task::{Context, Poll};
+use futures::future::BoxFuture;
+use http::header::AUTHORIZATION;
+use http::{HeaderValue, Request, Response};
+use http_body::Body;
+use http_body_util::Full;
+use std::error::Error as StdError;
+use std::sync::Arc;
+use tower_layer::Layer;
+use tower_service::Service;
+use url::Url;
+
+/// Tower layer authenticating requests as a GitHub App installation.
+///
+/// Requests to the API origin of the context (see [`GithubUrl`]) that have no `Authorization` header receive the
+/// auth of the [`InstallationAuth`]: queries can be sent without setting their `auth`. The access token is created
+/// through the inner service. Requests to other origins, such as the storage host of release assets, are sent as is.
+///
+/// It is intended to wrap the inner service of [`HttpGithubClient`].
+#[derive(Debug)]
+pub struct InstallationAuthLayer<Cx> {
+  auth: Arc<InstallationAuth>,
+  context: Cx,
+}
+
+impl<Cx: Clone> Clone for InstallationAuthLayer<Cx> {
+  fn clone(&self) -> Self {
+    Self {
+      auth: Arc::clone(&self.auth),
+      context: self.context.clone(),
+    }
+  }
+}
+
+impl<Cx> InstallationAuthLayer<Cx> {
+  /// Layer using `auth`; `context` is used to create the access tokens.
+  pub fn new(auth: Arc<InstallationAuth>, context: Cx) -> Self {
+    Self { auth, context }
+  }
+}
+
+impl<S, Cx: Clone> Layer<S> for InstallationAuthLayer<Cx> {
+  type Service = WithInstallationAuth<S, Cx>;
+
+  fn layer(&self, inner: S) -> Self::Service {
+    WithInstallationAuth {
+      inner,
+      auth: Arc::clone(&self.auth),
+      context: self.context.clone(),
+    }
+  }
+}
+
+/// Service created by [`InstallationAuthLayer`]
+#[derive(Debug)]
+pub struct WithInstallationAuth<S, Cx> {
+  inner: S,
+  auth: Arc<InstallationAuth>,
+  context: Cx,
+}
+
+impl<S: Clone, Cx: Clone> Clone for WithInstallationAuth<S, Cx> {
+  fn clone(&self) -> Self {
+    Self {
+      inner: self.inner.clone(),
+      auth: Arc::clone(&self.auth),
+      context: self.context.clone(),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, thiserror::Error)]
+pub enum InstallationAuthError<E> {
+  #[error(transparent)]
+  Inner(E),
+  #[error(transparent)]
+  Auth(GithubAppError),
+}
+
+impl<S, Cx, TyBody> Service<Request<Full<Bytes>>> for WithInstallationAuth<S, Cx>
+where
+  S: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + Clone + Send + 'static,
+  S::Error: StdError,
+  S::Future: Send,
+  Cx: GetRef<GithubUrl> + GetRef<UserAgent> + Clone + Send + Sync + 'static,
+  TyBody: Body + Send,
+  TyBody::Data: Send,
+  TyBody::Error: StdError,
+{
+  type Response = Response<TyBody>;
+  type Error = InstallationAuthError<S::Error>;
+  type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx).map_err(InstallationAuthError::Inner)
+  }
+
+  fn call(&mut self, mut req: Request<Full<Bytes>>) -> Self::Future {
+    let is_api = Url::parse(&req.uri().to_string())
+      .is_ok_and(|url| is_api_origin(GetRef::<GithubUrl>::get_ref(&self.context), &url.origin()));
+    if !is_api || req.headers().contains_key(AUTHORIZATION) {
+      let res = self.inner.call(req);
+      return Box::pin(async move { res.await.map_err(InstallationAuthError::Inner) });
+    }
+
+    // Keep the service that was polled ready for the request, and use a clone to create the access token.
+    let clone = self.inner.clone();
+    let mut inner = core::mem::replace(&mut self.inner, clone);
+    let token_client = HttpGithubClient::new(self.inner.clone());
+    let auth = Arc::clone(&self.auth);
+    let context = self.context.clone();
+    Box::pin(async move {
+      let auth = auth
+        .get_with(&context, |query| async move {
+          let mut client = token_client;
+          client.call(&query).await.map_err(|e| format!("{e:?}"))
+        })
+        .await
+        .map_err(InstallationAuthError::Auth)?;
+      let (key, value) = auth.as_view().http_header();
+      let mut value = HeaderValue::try_from(value)
+        .map_err(|e| InstallationAuthError::Auth(GithubAppError::InstallationAccessToken(format!("{e:?}"))))?;
+      value.set_sensitive(true);
+      req.headers_mut().insert(key, value);
+      inner.call(req).await.map_err(InstallationAuthError::Inner)
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::app::GithubAppKey;
+  use crate::common::app::{AppId, InstallationId};
+  use crate::common::project::{ProjectSlug, RepositoryRef};
+  use crate::context::Context as GithubContext;
+  use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+  use crate::GithubClient;
+  use chrono::{Duration, Utc};
+  use compact_str::CompactString;
+  use std::convert::Infallible;
+  use std::future::{ready, Ready};
+  use std::sync::Mutex;
+
+  const PRIVATE_KEY: &[u8] = include_bytes!("../../test-resources/github-app/private-key.pem");
+
+  /// Inner service creating installation access tokens and listing no releases, recording the requests
+  #[derive(Clone, Default)]
+  struct Server {
+    requests: Arc<Mutex<Vec<Request<Full<Bytes>>>>>,
+  }
+
+  impl Service<Request<Full<Bytes>>> for Server {
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+      let body = if req.uri().path().ends_with("/access_tokens") {
+        format!(
+          r#"{{"token":"ghs_installation","expires_at":"{}"}}"#,
+          (Utc::now() + Duration::hours(1)).to_rfc3339()
+        )
+      } else {
+        String::from("[]")
+      };
+      self.requests.lock().unwrap().push(req);
+      ready(Ok(Response::new(Full::new(Bytes::from(body)))))
+    }
+  }
+
+  #[tokio::test]
+  async fn send_query_with_installation_auth() {
+    let key = GithubAppKey::from_rsa_pem(AppId::new(123), PRIVATE_KEY).unwrap();
+    let auth = Arc::new(InstallationAuth::new(key, InstallationId::new(456)));
+    let context = GithubContext::new()
+      .set_github_url(GithubUrl(Url::parse("https://api.github.com/").unwrap()))
+      .set_user_agent(UserAgent::from_static("katal_github_client_test/0.0.0"));
+    let server = Server::default();
+    let mut client = HttpGithubClient::new(InstallationAuthLayer::new(auth, context.clone()).layer(server.clone()));
+    let query = GetProjectReleaseListQuery::new(RepositoryRef::Slug(ProjectSlug::new(
+      CompactString::new("octocat"),
+      CompactString::new("hello"),
+    )))
+    .set_context(context);
+    assert!(query.auth.is_none());
+
+    for _ in 0..2 {
+      let page = (&mut client).get_project_release_list(&query).await.unwrap();
+      assert_eq!(page.items, []);
+    }
+
+    let requests = server.requests.lock().unwrap();
+    let paths: Vec<&str> = requests.iter().map(|req| req.uri().path()).collect();
+    assert_eq!(
+      paths,
+      [
+        "/app/installations/456/access_tokens",
+        "/repos/octocat/hello/releases",
+        "/repos/octocat/hello/releases",
+      ]
+    );
+    // The access token is created with the app JWT, then reused
+    let auth: Vec<&str> = requests
+      .iter()
+      .map(|req| req.headers()[AUTHORIZATION].to_str().unwrap())
+      .collect();
+    assert!(auth[0].starts_with("Bearer ey"));
+    assert_eq!(auth[1..], ["Bearer ghs_installation", "Bearer ghs_installation"]);
+  }
+}
@@ -1,2 +1,14 @@
 #[cfg(feature = "http")]
+pub mod body;
+#[cfg(feature = "cache")]
+pub mod cache;
+#[cfg(feature = "http")]
 pub mod http;
+#[cfg(all(feature = "app", feature = "http"))]
+pub mod installation_auth;
+#[cfg(feature = "reqwest")]
+pub mod reqwest;
+#[cfg(feature = "http")]
+pub mod retry;
+#[cfg(feature = "http")]
+pub mod vcr;
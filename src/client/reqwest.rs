@@ -0,0 +1,50 @@
+use crate::client::http::HttpGithubClient;
+use bytes::Bytes;
+use core::task::{Context, Poll};
+use futures::future::BoxFuture;
+use http::{Request, Response};
+use http_body_util::Full;
+use tower_service::Service;
+
+/// GitHub client backed by a `reqwest` client.
+///
+/// This is the regular [`HttpGithubClient`] using [`ReqwestTransport`] to send its requests: it supports the same
+/// queries and has the same behavior regarding pagination cursors and errors.
+pub type ReqwestGithubClient = HttpGithubClient<ReqwestTransport>;
+
+impl ReqwestGithubClient {
+  pub fn from_reqwest(client: ::reqwest::Client) -> Self {
+    Self::new(ReqwestTransport::new(client))
+  }
+}
+
+/// Adapter exposing a `reqwest` client as a tower service over `http` requests.
+#[derive(Debug, Clone)]
+pub struct ReqwestTransport {
+  client: ::reqwest::Client,
+}
+
+impl ReqwestTransport {
+  pub fn new(client: ::reqwest::Client) -> Self {
+    Self { client }
+  }
+}
+
+impl Service<Request<Full<Bytes>>> for ReqwestTransport {
+  type Response = Response<::reqwest::Body>;
+  type Error = ::reqwest::Error;
+  type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+    let client = self.client.clone();
+    Box::pin(async move {
+      let req = ::reqwest::Request::try_from(req.map(::reqwest::Body::wrap))?;
+      let res = client.execute(req).await?;
+      Ok(Response::from(res))
+    })
+  }
+}
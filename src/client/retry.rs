@@ -0,0 +1,433 @@
+use crate::client::body::ResponseBody;
+use crate::client::http::{get_header, is_rate_limited};
+use crate::common::error::GithubErrorBody;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use core::task::{Context, Poll};
+use futures::future::BoxFuture;
+use http::header::RETRY_AFTER;
+use http::{HeaderMap, Method, Request, Response, StatusCode};
+use http_body::Body;
+use http_body_util::{BodyExt, Full};
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::Duration;
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Source of time for the retry layer
+///
+/// Tests can provide their own implementation to avoid waiting for real.
+pub trait Clock: Clone + Send + Sync + 'static {
+  fn now(&self) -> DateTime<Utc>;
+
+  fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()>;
+}
+
+/// Clock using the system time and timers
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+  fn now(&self) -> DateTime<Utc> {
+    Utc::now()
+  }
+
+  fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+    Box::pin(futures_timer::Delay::new(duration))
+  }
+}
+
+/// Configuration of the retry layer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RetryPolicy {
+  /// Maximum number of retries after the initial attempt
+  pub max_retries: u32,
+  /// Delay before the first retry, doubled on each subsequent retry
+  pub base_delay: Duration,
+  /// Upper bound for the exponential backoff delay
+  pub max_delay: Duration,
+  /// Maximum delay the server may ask for through `retry-after` or `x-ratelimit-reset`.
+  ///
+  /// If the server asks to wait longer, the response is returned as-is.
+  pub max_wait: Duration,
+  /// Randomize backoff delays to spread out retries from concurrent clients
+  pub jitter: bool,
+  /// Also retry requests with methods other than `GET`, `HEAD` and `OPTIONS`, such as `POST`, `PUT`, `PATCH` or
+  /// `DELETE`.
+  ///
+  /// A `PUT` or `DELETE` is idempotent, but it may have been applied before the error: retrying it could for example
+  /// replace changes made in the meantime, or report a `404` for a deletion that succeeded.
+  pub retry_non_idempotent: bool,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self {
+      max_retries: 3,
+      base_delay: Duration::from_millis(500),
+      max_delay: Duration::from_secs(30),
+      max_wait: Duration::from_secs(15 * 60),
+      jitter: true,
+      retry_non_idempotent: false,
+    }
+  }
+}
+
+impl RetryPolicy {
+  /// Exponential backoff delay before the retry number `retry` (starting at 0)
+  fn backoff(&self, retry: u32) -> Duration {
+    let delay = self
+      .base_delay
+      .saturating_mul(2u32.saturating_pow(retry))
+      .min(self.max_delay);
+    if self.jitter {
+      // "equal jitter": keep half of the delay, randomize the other half
+      let half = delay / 2;
+      let random = RandomState::new().build_hasher().finish();
+      half + Duration::from_nanos(random % (half.as_nanos() as u64 + 1))
+    } else {
+      delay
+    }
+  }
+}
+
+/// Tower layer retrying failed GitHub requests.
+///
+/// Transient server errors (`500`, `502`, `503`, `504`), transport errors and rate limit errors are retried with
+/// exponential backoff. When the response says how long to wait (`retry-after` as seconds or as an HTTP date, or
+/// `x-ratelimit-reset` once the rate limit is exhausted), this delay is used instead. Rate limits are detected the same
+/// way as [`HttpGithubClientError::RateLimited`](crate::client::http::HttpGithubClientError::RateLimited): the body of
+/// `403` responses is buffered to check for secondary rate limit messages. Other response bodies are streamed from the
+/// inner service.
+///
+/// Only `GET`, `HEAD` and `OPTIONS` requests are retried, unless [`RetryPolicy::retry_non_idempotent`] is set.
+///
+/// It is intended to wrap the inner service of [`HttpGithubClient`](crate::client::http::HttpGithubClient).
+#[derive(Debug, Clone)]
+pub struct RetryLayer<C = SystemClock> {
+  policy: RetryPolicy,
+  clock: C,
+}
+
+impl RetryLayer {
+  pub fn new(policy: RetryPolicy) -> Self {
+    Self {
+      policy,
+      clock: SystemClock,
+    }
+  }
+}
+
+impl<C> RetryLayer<C> {
+  pub fn set_clock<NewC>(self, clock: NewC) -> RetryLayer<NewC> {
+    RetryLayer {
+      policy: self.policy,
+      clock,
+    }
+  }
+}
+
+impl<S, C: Clone> Layer<S> for RetryLayer<C> {
+  type Service = Retry<S, C>;
+
+  fn layer(&self, inner: S) -> Self::Service {
+    Retry {
+      inner,
+      policy: self.policy,
+      clock: self.clock.clone(),
+    }
+  }
+}
+
+/// Service created by [`RetryLayer`]
+#[derive(Debug, Clone)]
+pub struct Retry<S, C = SystemClock> {
+  inner: S,
+  policy: RetryPolicy,
+  clock: C,
+}
+
+impl<S, C, TyBody> Service<Request<Full<Bytes>>> for Retry<S, C>
+where
+  S: Service<Request<Full<Bytes>>, Response = Response<TyBody>> + Clone + Send + 'static,
+  S::Error: Send,
+  S::Future: Send,
+  C: Clock,
+  TyBody: Body<Data = Bytes> + Send + 'static,
+{
+  type Response = Response<ResponseBody<TyBody>>;
+  type Error = S::Error;
+  type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    self.inner.poll_ready(cx)
+  }
+
+  fn call(&mut self, req: Request<Full<Bytes>>) -> Self::Future {
+    // Keep the service that was polled ready for the first attempt, see `tower::Service` docs.
+    let clone = self.inner.clone();
+    let mut inner = core::mem::replace(&mut self.inner, clone);
+    let policy = self.policy;
+    let clock = self.clock.clone();
+    let retryable = policy.retry_non_idempotent || [Method::GET, Method::HEAD, Method::OPTIONS].contains(req.method());
+
+    Box::pin(async move {
+      let mut retry: u32 = 0;
+      loop {
+        let res = inner.call(clone_request(&req)).await;
+        if !retryable || retry >= policy.max_retries {
+          return res.map(|response| response.map(ResponseBody::inner));
+        }
+        let delay = match res {
+          Ok(response) => {
+            let (response, body) = read_forbidden_body(response).await;
+            let delay = retry_delay(
+              &policy,
+              &clock,
+              retry,
+              response.status(),
+              response.headers(),
+              body.as_ref(),
+            );
+            match delay {
+              Some(delay) => delay,
+              None => return Ok(response),
+            }
+          }
+          Err(_) => policy.backoff(retry),
+        };
+        clock.sleep(delay).await;
+        retry += 1;
+        futures::future::poll_fn(|cx| inner.poll_ready(cx)).await?;
+      }
+    })
+  }
+}
+
+/// Delay to wait before retrying, or `None` if the response should be returned.
+fn retry_delay<C: Clock>(
+  policy: &RetryPolicy,
+  clock: &C,
+  retry: u32,
+  status: StatusCode,
+  headers: &HeaderMap,
+  body: Option<&GithubErrorBody>,
+) -> Option<Duration> {
+  if is_rate_limited(status, headers, body) {
+    let wait = if let Some(retry_after) = retry_after(clock, headers) {
+      retry_after
+    } else if let Some(reset) = get_header::<i64>(headers, "x-ratelimit-reset") {
+      let reset = DateTime::from_timestamp(reset, 0)?;
+      (reset - clock.now()).to_std().unwrap_or(Duration::ZERO)
+    } else {
+      policy.backoff(retry)
+    };
+    return if wait <= policy.max_wait { Some(wait) } else { None };
+  }
+  match status {
+    StatusCode::INTERNAL_SERVER_ERROR
+    | StatusCode::BAD_GATEWAY
+    | StatusCode::SERVICE_UNAVAILABLE
+    | StatusCode::GATEWAY_TIMEOUT => Some(policy.backoff(retry)),
+    _ => None,
+  }
+}
+
+/// Delay from the `retry-after` header, either a number of seconds or an HTTP date
+fn retry_after<C: Clock>(clock: &C, headers: &HeaderMap) -> Option<Duration> {
+  let value = get_header::<String>(headers, RETRY_AFTER.as_str())?;
+  if let Ok(seconds) = value.parse::<u64>() {
+    return Some(Duration::from_secs(seconds));
+  }
+  let date = DateTime::parse_from_rfc2822(&value).ok()?;
+  Some(
+    (date.with_timezone(&Utc) - clock.now())
+      .to_std()
+      .unwrap_or(Duration::ZERO),
+  )
+}
+
+fn clone_request(req: &Request<Full<Bytes>>) -> Request<Full<Bytes>> {
+  let mut clone = Request::new(req.body().clone());
+  *clone.method_mut() = req.method().clone();
+  *clone.uri_mut() = req.uri().clone();
+  *clone.version_mut() = req.version();
+  *clone.headers_mut() = req.headers().clone();
+  clone
+}
+
+/// Parse the error body of `403` responses, unless the headers already show a rate limit.
+///
+/// Secondary rate limits are only identified by their message. The body is put back into the response; if it cannot
+/// be read, the response is returned with an empty body. Other responses keep the body of the inner service.
+async fn read_forbidden_body<TyBody>(
+  response: Response<TyBody>,
+) -> (Response<ResponseBody<TyBody>>, Option<GithubErrorBody>)
+where
+  TyBody: Body,
+{
+  if response.status() != StatusCode::FORBIDDEN || is_rate_limited(response.status(), response.headers(), None) {
+    return (response.map(ResponseBody::inner), None);
+  }
+  let (parts, body) = response.into_parts();
+  let body = match body.collect().await {
+    Ok(body) => body.to_bytes(),
+    Err(_) => Bytes::new(),
+  };
+  let error: Option<GithubErrorBody> = serde_json::from_slice(&body).ok();
+  (Response::from_parts(parts, ResponseBody::buffered(body)), error)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use std::collections::VecDeque;
+  use std::convert::Infallible;
+  use std::future::{ready, Ready};
+  use std::sync::{Arc, Mutex};
+
+  /// Clock frozen at a fixed time, recording requested sleeps
+  #[derive(Clone)]
+  struct MockClock {
+    now: DateTime<Utc>,
+    sleeps: Arc<Mutex<Vec<Duration>>>,
+  }
+
+  impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+      self.now
+    }
+
+    fn sleep(&self, duration: Duration) -> BoxFuture<'static, ()> {
+      self.sleeps.lock().unwrap().push(duration);
+      Box::pin(ready(()))
+    }
+  }
+
+  /// Inner service replying with a predefined list of responses
+  #[derive(Clone)]
+  struct Script {
+    responses: Arc<Mutex<VecDeque<Response<Full<Bytes>>>>>,
+    calls: Arc<Mutex<u32>>,
+  }
+
+  impl Service<Request<Full<Bytes>>> for Script {
+    type Response = Response<Full<Bytes>>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: Request<Full<Bytes>>) -> Self::Future {
+      *self.calls.lock().unwrap() += 1;
+      ready(Ok(
+        self.responses.lock().unwrap().pop_front().expect("unexpected request"),
+      ))
+    }
+  }
+
+  fn response(status: u16, headers: &[(&'static str, &'static str)]) -> Response<Full<Bytes>> {
+    let mut builder = Response::builder().status(status);
+    for (name, value) in headers {
+      builder = builder.header(*name, *value);
+    }
+    builder.body(Full::new(Bytes::new())).unwrap()
+  }
+
+  fn setup(responses: Vec<Response<Full<Bytes>>>) -> (Retry<Script, MockClock>, MockClock, Script) {
+    let clock = MockClock {
+      now: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+      sleeps: Arc::new(Mutex::new(Vec::new())),
+    };
+    let script = Script {
+      responses: Arc::new(Mutex::new(responses.into())),
+      calls: Arc::new(Mutex::new(0)),
+    };
+    let policy = RetryPolicy {
+      jitter: false,
+      ..RetryPolicy::default()
+    };
+    let service = RetryLayer::new(policy).set_clock(clock.clone()).layer(script.clone());
+    (service, clock, script)
+  }
+
+  fn request(method: Method) -> Request<Full<Bytes>> {
+    Request::builder()
+      .method(method)
+      .uri("https://api.github.com/repos/rust-lang/rust/releases")
+      .body(Full::new(Bytes::new()))
+      .unwrap()
+  }
+
+  #[tokio::test]
+  async fn retry_server_errors_with_backoff() {
+    let (mut service, clock, script) = setup(vec![response(502, &[]), response(503, &[]), response(200, &[])]);
+    let res = service.call(request(Method::GET)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(*script.calls.lock().unwrap(), 3);
+    assert_eq!(
+      *clock.sleeps.lock().unwrap(),
+      vec![Duration::from_millis(500), Duration::from_millis(1000)]
+    );
+  }
+
+  #[tokio::test]
+  async fn wait_for_rate_limit_reset() {
+    let (mut service, clock, _) = setup(vec![
+      response(
+        403,
+        &[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "1700000042")],
+      ),
+      response(429, &[("retry-after", "7")]),
+      // 2023-11-14T22:13:20Z + 90s
+      response(429, &[("retry-after", "Tue, 14 Nov 2023 22:14:50 GMT")]),
+      response(200, &[]),
+    ]);
+    let res = service.call(request(Method::GET)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+      *clock.sleeps.lock().unwrap(),
+      vec![Duration::from_secs(42), Duration::from_secs(7), Duration::from_secs(90)]
+    );
+  }
+
+  #[tokio::test]
+  async fn retry_secondary_rate_limit() {
+    let secondary = Response::builder()
+      .status(403)
+      .body(Full::new(Bytes::from_static(
+        br#"{"message":"You have exceeded a secondary rate limit. Please wait a few minutes before you try again."}"#,
+      )))
+      .unwrap();
+    let forbidden = Response::builder()
+      .status(403)
+      .body(Full::new(Bytes::from_static(
+        br#"{"message":"Resource not accessible by integration"}"#,
+      )))
+      .unwrap();
+    let (mut service, clock, script) = setup(vec![secondary, forbidden]);
+    let res = service.call(request(Method::GET)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::FORBIDDEN);
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(
+      body,
+      Bytes::from_static(br#"{"message":"Resource not accessible by integration"}"#)
+    );
+    assert_eq!(*script.calls.lock().unwrap(), 2);
+    assert_eq!(*clock.sleeps.lock().unwrap(), vec![Duration::from_millis(500)]);
+  }
+
+  #[tokio::test]
+  async fn do_not_retry_mutations() {
+    for method in [Method::POST, Method::PUT, Method::PATCH, Method::DELETE] {
+      let (mut service, clock, script) = setup(vec![response(502, &[])]);
+      let res = service.call(request(method.clone())).await.unwrap();
+      assert_eq!(res.status(), StatusCode::BAD_GATEWAY, "{method}");
+      assert_eq!(*script.calls.lock().unwrap(), 1, "{method}");
+      assert!(clock.sleeps.lock().unwrap().is_empty(), "{method}");
+    }
+  }
+}
@@ -0,0 +1,210 @@
+use compact_str::CompactString;
+use core::fmt;
+use core::str::FromStr;
+#[cfg(feature = "verify")]
+use sha2::Digest as _;
+use std::collections::BTreeMap;
+
+/// Checksum of some content, in the `<algorithm>:<hex>` format used by GitHub (e.g. `sha256:9f86d0...`)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Digest {
+  Sha256([u8; 32]),
+  Sha512([u8; 64]),
+  /// Digest using an algorithm unknown to this library, kept as received
+  Other(CompactString),
+}
+
+impl Digest {
+  /// Name of the algorithm, as used in the `<algorithm>:<hex>` format
+  pub fn algorithm(&self) -> &str {
+    match self {
+      Self::Sha256(_) => "sha256",
+      Self::Sha512(_) => "sha512",
+      Self::Other(raw) => raw.split_once(':').map_or(raw.as_str(), |(algorithm, _)| algorithm),
+    }
+  }
+
+  /// Start hashing content with the same algorithm as this digest.
+  #[cfg(feature = "verify")]
+  pub fn hasher(&self) -> Result<DigestHasher, DigestError> {
+    match self {
+      Self::Sha256(_) => Ok(DigestHasher::Sha256(sha2::Sha256::new())),
+      Self::Sha512(_) => Ok(DigestHasher::Sha512(sha2::Sha512::new())),
+      Self::Other(_) => Err(DigestError::UnsupportedAlgorithm(CompactString::new(self.algorithm()))),
+    }
+  }
+}
+
+impl fmt::Display for Digest {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let bytes: &[u8] = match self {
+      Self::Sha256(bytes) => bytes,
+      Self::Sha512(bytes) => bytes,
+      Self::Other(raw) => return f.write_str(raw),
+    };
+    f.write_str(self.algorithm())?;
+    f.write_str(":")?;
+    bytes.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+  }
+}
+
+impl FromStr for Digest {
+  type Err = DigestError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let (algorithm, hex) = s
+      .split_once(':')
+      .ok_or_else(|| DigestError::Invalid(CompactString::new(s)))?;
+    let invalid = || DigestError::Invalid(CompactString::new(s));
+    match algorithm {
+      "sha256" => decode_hex(hex).map(Self::Sha256).ok_or_else(invalid),
+      "sha512" => decode_hex(hex).map(Self::Sha512).ok_or_else(invalid),
+      _ => Ok(Self::Other(CompactString::new(s))),
+    }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Digest {
+  fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(self)
+  }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Digest {
+  fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let raw = CompactString::deserialize(deserializer)?;
+    raw.parse().map_err(serde::de::Error::custom)
+  }
+}
+
+/// Incremental hash computation, created with [`Digest::hasher`]
+#[cfg(feature = "verify")]
+#[derive(Debug, Clone)]
+pub enum DigestHasher {
+  Sha256(sha2::Sha256),
+  Sha512(sha2::Sha512),
+}
+
+#[cfg(feature = "verify")]
+impl DigestHasher {
+  pub fn update(&mut self, data: &[u8]) {
+    match self {
+      Self::Sha256(hasher) => hasher.update(data),
+      Self::Sha512(hasher) => hasher.update(data),
+    }
+  }
+
+  pub fn finalize(self) -> Digest {
+    match self {
+      Self::Sha256(hasher) => Digest::Sha256(hasher.finalize().into()),
+      Self::Sha512(hasher) => Digest::Sha512(hasher.finalize().into()),
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum DigestError {
+  #[error("invalid digest: {0}")]
+  Invalid(CompactString),
+  #[error("unsupported digest algorithm: {0}")]
+  UnsupportedAlgorithm(CompactString),
+  #[error("invalid checksum manifest line {0}")]
+  InvalidManifest(usize),
+  #[error("cannot verify content starting at offset {0}")]
+  PartialContent(u64),
+  #[error("digest mismatch: expected {expected}, got {actual}")]
+  Mismatch { expected: Box<Digest>, actual: Box<Digest> },
+}
+
+/// Checksums of files, read from a `SHA256SUMS`-style manifest
+///
+/// Each line is a hex SHA-256 digest followed by the file name, separated by a space and a space or `*` (the format
+/// of `sha256sum`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChecksumManifest {
+  /// Digests, by file name
+  digests: BTreeMap<CompactString, Digest>,
+}
+
+impl ChecksumManifest {
+  pub fn parse(manifest: &str) -> Result<Self, DigestError> {
+    let mut digests = BTreeMap::new();
+    for (index, line) in manifest.lines().enumerate() {
+      let line = line.trim_end();
+      if line.is_empty() {
+        continue;
+      }
+      let invalid = || DigestError::InvalidManifest(index + 1);
+      let (hex, name) = line.split_once(' ').ok_or_else(invalid)?;
+      let name = name.strip_prefix([' ', '*']).ok_or_else(invalid)?;
+      let name = name.strip_prefix("./").unwrap_or(name);
+      let digest = decode_hex(hex).map(Digest::Sha256).ok_or_else(invalid)?;
+      digests.insert(CompactString::new(name), digest);
+    }
+    Ok(Self { digests })
+  }
+
+  /// Digest of the file `name`
+  pub fn get(&self, name: &str) -> Option<&Digest> {
+    self.digests.get(name)
+  }
+}
+
+fn decode_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+  if hex.len() != N * 2 {
+    return None;
+  }
+  let mut bytes = [0; N];
+  for (byte, pair) in bytes.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+    *byte = u8::from_str_radix(core::str::from_utf8(pair).ok()?, 16).ok()?;
+  }
+  Some(bytes)
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+  #[test]
+  fn parse_and_format_digest() {
+    let raw = format!("sha256:{HELLO_SHA256}");
+    let digest: Digest = raw.parse().unwrap();
+    assert_eq!(digest.to_string(), raw);
+    assert!("sha256:abc".parse::<Digest>().is_err());
+  }
+
+  #[test]
+  #[cfg(feature = "verify")]
+  fn hash_with_digest_algorithm() {
+    let digest: Digest = format!("sha256:{HELLO_SHA256}").parse().unwrap();
+    let mut hasher = digest.hasher().unwrap();
+    hasher.update(b"hello");
+    assert_eq!(hasher.finalize(), digest);
+
+    assert_eq!(
+      "md5:5d41402abc4b2a76b9719d911017c592"
+        .parse::<Digest>()
+        .map(|digest| digest.hasher().err()),
+      Ok(Some(DigestError::UnsupportedAlgorithm(CompactString::new("md5"))))
+    );
+  }
+
+  #[test]
+  fn parse_checksum_manifest() {
+    let manifest = ChecksumManifest::parse(&format!(
+      "{HELLO_SHA256}  hello.tar.gz\n{HELLO_SHA256} *./hello.zip\n\n"
+    ))
+    .unwrap();
+    let expected = Digest::Sha256(decode_hex(HELLO_SHA256).unwrap());
+    assert_eq!(manifest.get("hello.tar.gz"), Some(&expected));
+    assert_eq!(manifest.get("hello.zip"), Some(&expected));
+    assert_eq!(
+      ChecksumManifest::parse("hello.tar.gz"),
+      Err(DigestError::InvalidManifest(1))
+    );
+  }
+}
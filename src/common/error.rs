@@ -0,0 +1,68 @@
+use compact_str::CompactString;
+use core::fmt;
+
+/// Error body returned by the GitHub API
+///
+/// <https://docs.github.com/en/rest/using-the-rest-api/troubleshooting-the-rest-api?apiVersion=2022-11-28>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GithubErrorBody {
+  pub message: CompactString,
+  pub documentation_url: Option<CompactString>,
+  pub status: Option<CompactString>,
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub errors: Vec<GithubValidationError>,
+}
+
+impl fmt::Display for GithubErrorBody {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.message.as_str())?;
+    for error in &self.errors {
+      write!(f, "; {error}")?;
+    }
+    Ok(())
+  }
+}
+
+/// Details about an invalid input, found in the `errors` list of a `422 Unprocessable Entity` response
+///
+/// <https://docs.github.com/en/rest/using-the-rest-api/troubleshooting-the-rest-api?apiVersion=2022-11-28#validation-failed>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GithubValidationError {
+  pub resource: Option<CompactString>,
+  pub field: Option<CompactString>,
+  pub code: CompactString,
+  pub message: Option<CompactString>,
+}
+
+impl GithubValidationError {
+  /// Error code for a missing resource
+  pub const MISSING: &'static str = "missing";
+  /// Error code for a missing required field
+  pub const MISSING_FIELD: &'static str = "missing_field";
+  /// Error code for a field with an invalid format
+  pub const INVALID: &'static str = "invalid";
+  /// Error code for a field conflicting with an existing resource
+  pub const ALREADY_EXISTS: &'static str = "already_exists";
+  /// Error code for an input that could not be processed
+  pub const UNPROCESSABLE: &'static str = "unprocessable";
+  /// Error code for a custom error, described by `message`
+  pub const CUSTOM: &'static str = "custom";
+}
+
+impl fmt::Display for GithubValidationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match (&self.resource, &self.field) {
+      (Some(resource), Some(field)) => write!(f, "{resource}.{field}: ")?,
+      (Some(resource), None) => write!(f, "{resource}: ")?,
+      (None, Some(field)) => write!(f, "{field}: ")?,
+      (None, None) => {}
+    }
+    f.write_str(self.code.as_str())?;
+    if let Some(message) = &self.message {
+      write!(f, " ({message})")?;
+    }
+    Ok(())
+  }
+}
@@ -1,6 +1,10 @@
+use chrono::{DateTime, Utc};
 use compact_str::CompactString;
 use std::num::NonZeroU8;
 
+pub mod app;
+pub mod digest;
+pub mod error;
 pub mod group;
 pub mod namespace;
 pub mod package;
@@ -154,5 +158,25 @@ pub struct Page<T> {
   pub next: Option<CompactString>,
   /// Cursor to the last page
   pub last: Option<CompactString>,
+  /// Rate limit status reported with this page
+  pub rate_limit: Option<RateLimit>,
   pub items: Vec<T>,
 }
+
+/// Rate limit status, as reported by the `x-ratelimit-*` response headers
+///
+/// See <https://docs.github.com/en/rest/using-the-rest-api/rate-limits-for-the-rest-api?apiVersion=2022-11-28>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RateLimit {
+  /// Maximum number of requests allowed in the current window
+  pub limit: u64,
+  /// Number of requests remaining in the current window
+  pub remaining: u64,
+  /// Number of requests made in the current window
+  pub used: u64,
+  /// Time when the current window resets
+  pub reset: DateTime<Utc>,
+  /// Rate limit bucket the request counted against (`core`, `search`, ...)
+  pub resource: Option<CompactString>,
+}
@@ -1,17 +1,71 @@
+use crate::common::digest::Digest;
+use crate::common::user::SimpleUser;
+use crate::url_util::UriTemplate;
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use compact_str::CompactString;
+use core::fmt;
+use futures::stream::BoxStream;
 use url::Url;
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReleaseId(u64);
+
+impl ReleaseId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+
+  /// Calls `f` with the string representation of this id as an argument.
+  #[inline]
+  pub fn with_str<R, F>(self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&'a str) -> R,
+  {
+    let mut buf = ::itoa::Buffer::new();
+    f(buf.format(self.0))
+  }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReleaseAssetId(u64);
+
+impl ReleaseAssetId {
+  pub const fn new(id: u64) -> Self {
+    Self(id)
+  }
+
+  pub const fn into_u64(self) -> u64 {
+    self.0
+  }
+
+  /// Calls `f` with the string representation of this id as an argument.
+  #[inline]
+  pub fn with_str<R, F>(self, f: F) -> R
+  where
+    F: for<'a> FnOnce(&'a str) -> R,
+  {
+    let mut buf = ::itoa::Buffer::new();
+    f(buf.format(self.0))
+  }
+}
+
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Release {
   pub url: Url,
   pub html_url: Url,
   pub assets_url: Url,
-  pub upload_url: Url,
+  pub upload_url: UriTemplate,
   pub tarball_url: Option<Url>,
   pub zipball_url: Option<Url>,
-  pub id: u32,
+  pub id: ReleaseId,
   pub node_id: String,
   pub tag_name: String,
   pub target_commitish: String,
@@ -21,8 +75,24 @@ pub struct Release {
   pub prerelease: bool,
   pub created_at: DateTime<Utc>,
   pub published_at: Option<DateTime<Utc>>,
-  // pub author: ...,
+  pub author: SimpleUser,
   pub assets: Vec<ReleaseAsset>,
+  /// Reactions to the release, omitted when there are none
+  #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+  pub reactions: Option<ReactionRollup>,
+  /// Number of users mentioned in the body, omitted when there are none
+  #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+  pub mentions_count: Option<u32>,
+}
+
+impl Release {
+  /// Asset listing the checksums of the other assets, such as `SHA256SUMS` or `checksums.txt`
+  pub fn checksum_manifest(&self) -> Option<&ReleaseAsset> {
+    self.assets.iter().find(|asset| {
+      let name = asset.name.to_ascii_lowercase();
+      name == "sha256sums" || name == "sha256sums.txt" || name.ends_with("checksums.txt")
+    })
+  }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -30,17 +100,38 @@ pub struct Release {
 pub struct ReleaseAsset {
   pub url: Url,
   pub browser_download_url: Url,
-  pub id: u32,
+  pub id: ReleaseAssetId,
   pub node_id: String,
   pub name: String,
   pub label: Option<String>,
   pub state: String, // uploaded/open
   pub content_type: String,
-  pub size: u32,
-  pub download_count: u32,
+  pub size: u64,
+  /// Checksum computed by GitHub, missing for assets uploaded before digests were introduced
+  #[cfg_attr(feature = "serde", serde(default))]
+  pub digest: Option<Digest>,
+  pub download_count: u64,
   pub created_at: DateTime<Utc>,
   pub updated_at: DateTime<Utc>,
-  // pub uploader: ...,
+  pub uploader: Option<SimpleUser>,
+}
+
+/// Counts of the reactions to a resource
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReactionRollup {
+  pub url: Url,
+  pub total_count: u32,
+  #[cfg_attr(feature = "serde", serde(rename = "+1"))]
+  pub plus_one: u32,
+  #[cfg_attr(feature = "serde", serde(rename = "-1"))]
+  pub minus_one: u32,
+  pub laugh: u32,
+  pub confused: u32,
+  pub heart: u32,
+  pub hooray: u32,
+  pub eyes: u32,
+  pub rocket: u32,
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -230,3 +321,226 @@ impl<Str: AsRef<str>> InputReleaseLink<Str> {
     }
   }
 }
+
+/// Whether a release should be set as the latest release of its repository
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MakeLatest {
+  #[cfg_attr(feature = "serde", serde(rename = "true"))]
+  True,
+  #[cfg_attr(feature = "serde", serde(rename = "false"))]
+  False,
+  /// Use the release with the most recent creation date and highest semantic version
+  #[cfg_attr(feature = "serde", serde(rename = "legacy"))]
+  Legacy,
+}
+
+impl MakeLatest {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      Self::True => "true",
+      Self::False => "false",
+      Self::Legacy => "legacy",
+    }
+  }
+}
+
+/// Body of the request creating a release
+///
+/// Fields set to `None` are omitted, so GitHub uses its defaults.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputRelease<Str = CompactString> {
+  pub tag_name: Str,
+  /// Branch or commit SHA used to create the tag if it does not exist yet (default branch by default)
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub target_commitish: Option<Str>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub name: Option<Str>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub body: Option<Str>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub draft: Option<bool>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub prerelease: Option<bool>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub make_latest: Option<MakeLatest>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub discussion_category_name: Option<Str>,
+  /// Generate the name and body automatically; `body` is prepended to the generated notes
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub generate_release_notes: Option<bool>,
+}
+
+pub type InputReleaseView<'req> = InputRelease<&'req str>;
+
+impl<Str> InputRelease<Str> {
+  pub const fn new(tag_name: Str) -> Self {
+    Self {
+      tag_name,
+      target_commitish: None,
+      name: None,
+      body: None,
+      draft: None,
+      prerelease: None,
+      make_latest: None,
+      discussion_category_name: None,
+      generate_release_notes: None,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> InputRelease<Str> {
+  pub fn as_view(&self) -> InputReleaseView<'_> {
+    InputReleaseView {
+      tag_name: self.tag_name.as_ref(),
+      target_commitish: self.target_commitish.as_ref().map(|s| s.as_ref()),
+      name: self.name.as_ref().map(|s| s.as_ref()),
+      body: self.body.as_ref().map(|s| s.as_ref()),
+      draft: self.draft,
+      prerelease: self.prerelease,
+      make_latest: self.make_latest,
+      discussion_category_name: self.discussion_category_name.as_ref().map(|s| s.as_ref()),
+      generate_release_notes: self.generate_release_notes,
+    }
+  }
+}
+
+/// Body of the request updating a release
+///
+/// Only the fields set to `Some` are changed.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputReleasePatch<Str = CompactString> {
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub tag_name: Option<Str>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub target_commitish: Option<Str>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub name: Option<Str>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub body: Option<Str>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub draft: Option<bool>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub prerelease: Option<bool>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub make_latest: Option<MakeLatest>,
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub discussion_category_name: Option<Str>,
+}
+
+pub type InputReleasePatchView<'req> = InputReleasePatch<&'req str>;
+
+impl<Str: AsRef<str>> InputReleasePatch<Str> {
+  pub fn as_view(&self) -> InputReleasePatchView<'_> {
+    InputReleasePatchView {
+      tag_name: self.tag_name.as_ref().map(|s| s.as_ref()),
+      target_commitish: self.target_commitish.as_ref().map(|s| s.as_ref()),
+      name: self.name.as_ref().map(|s| s.as_ref()),
+      body: self.body.as_ref().map(|s| s.as_ref()),
+      draft: self.draft,
+      prerelease: self.prerelease,
+      make_latest: self.make_latest,
+      discussion_category_name: self.discussion_category_name.as_ref().map(|s| s.as_ref()),
+    }
+  }
+}
+
+/// Body of the request updating a release asset
+///
+/// Only the fields set to `Some` are changed.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputReleaseAssetPatch<Str = CompactString> {
+  /// File name of the asset
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub name: Option<Str>,
+  /// Alternate short description of the asset, used in place of the file name
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub label: Option<Str>,
+  /// Only `uploaded` is accepted, to restore an asset
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub state: Option<Str>,
+}
+
+pub type InputReleaseAssetPatchView<'req> = InputReleaseAssetPatch<&'req str>;
+
+impl<Str: AsRef<str>> InputReleaseAssetPatch<Str> {
+  pub fn as_view(&self) -> InputReleaseAssetPatchView<'_> {
+    InputReleaseAssetPatchView {
+      name: self.name.as_ref().map(|s| s.as_ref()),
+      label: self.label.as_ref().map(|s| s.as_ref()),
+      state: self.state.as_ref().map(|s| s.as_ref()),
+    }
+  }
+}
+
+/// Body of the request generating release notes
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InputReleaseNotes<Str = CompactString> {
+  /// Tag of the release; it does not need to exist yet
+  pub tag_name: Str,
+  /// Branch or commit SHA used to create the tag if it does not exist yet (default branch by default)
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub target_commitish: Option<Str>,
+  /// Start of the range of changes (previous release by default)
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub previous_tag_name: Option<Str>,
+  /// Path of the configuration file in the repository (`.github/release.yml` by default)
+  #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+  pub configuration_file_path: Option<Str>,
+}
+
+pub type InputReleaseNotesView<'req> = InputReleaseNotes<&'req str>;
+
+impl<Str> InputReleaseNotes<Str> {
+  pub const fn new(tag_name: Str) -> Self {
+    Self {
+      tag_name,
+      target_commitish: None,
+      previous_tag_name: None,
+      configuration_file_path: None,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> InputReleaseNotes<Str> {
+  pub fn as_view(&self) -> InputReleaseNotesView<'_> {
+    InputReleaseNotesView {
+      tag_name: self.tag_name.as_ref(),
+      target_commitish: self.target_commitish.as_ref().map(|s| s.as_ref()),
+      previous_tag_name: self.previous_tag_name.as_ref().map(|s| s.as_ref()),
+      configuration_file_path: self.configuration_file_path.as_ref().map(|s| s.as_ref()),
+    }
+  }
+}
+
+/// Release notes generated by GitHub, to use as the name and body of a release
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ReleaseNotes {
+  pub name: String,
+  pub body: String,
+}
+
+/// Content of a release asset, received as a stream of chunks
+pub struct ReleaseAssetDownload {
+  /// Index of the first byte of `content` in the asset; `0` unless a requested range was honored
+  pub offset: u64,
+  /// Total size of the asset, if known
+  pub size: Option<u64>,
+  pub content_type: Option<CompactString>,
+  pub content: BoxStream<'static, Result<Bytes, std::io::Error>>,
+}
+
+impl fmt::Debug for ReleaseAssetDownload {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("ReleaseAssetDownload")
+      .field("offset", &self.offset)
+      .field("size", &self.size)
+      .field("content_type", &self.content_type)
+      .finish_non_exhaustive()
+  }
+}
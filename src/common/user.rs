@@ -36,3 +36,22 @@ pub struct User {
   pub avatar_url: Option<CompactString>,
   pub web_url: Url,
 }
+
+/// Public summary of a GitHub account, as embedded in other resources
+///
+/// <https://docs.github.com/en/rest/users/users?apiVersion=2022-11-28#get-a-user>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SimpleUser {
+  pub login: CompactString,
+  pub id: UserId,
+  pub node_id: CompactString,
+  pub avatar_url: Url,
+  pub gravatar_id: Option<CompactString>,
+  pub url: Url,
+  pub html_url: Url,
+  /// Kind of account: `User`, `Organization` or `Bot`
+  #[cfg_attr(feature = "serde", serde(rename = "type"))]
+  pub r#type: CompactString,
+  pub site_admin: bool,
+}
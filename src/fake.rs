@@ -0,0 +1,1304 @@
+use crate::client::http::HttpGithubClientError;
+use crate::common::app::InstallationAccessToken;
+use crate::common::digest::Digest;
+use crate::common::error::{GithubErrorBody, GithubValidationError};
+use crate::common::project::{ProjectId, ProjectSlug, RepositoryRef};
+use crate::common::release::{
+  InputRelease, InputReleaseAssetPatch, InputReleasePatch, InputReleaseView, Release, ReleaseAsset,
+  ReleaseAssetDownload, ReleaseAssetId, ReleaseId, ReleaseNotes,
+};
+use crate::common::user::{SimpleUser, UserId};
+use crate::common::Page;
+use crate::query::create_installation_access_token::CreateInstallationAccessTokenQuery;
+use crate::query::create_release::CreateReleaseQuery;
+use crate::query::delete_release::DeleteReleaseQuery;
+use crate::query::delete_release_asset::DeleteReleaseAssetQuery;
+use crate::query::download_release_asset::DownloadReleaseAssetQuery;
+use crate::query::generate_release_notes::GenerateReleaseNotesQuery;
+use crate::query::get_project_latest_release::GetProjectLatestReleaseQuery;
+use crate::query::get_project_release::GetProjectReleaseQuery;
+use crate::query::get_project_release_by_tag::GetProjectReleaseByTagQuery;
+use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
+use crate::query::get_release_asset::GetReleaseAssetQuery;
+use crate::query::get_release_asset_list::GetReleaseAssetListQuery;
+use crate::query::get_release_asset_list_page::GetReleaseAssetListPageQuery;
+use crate::query::update_release::UpdateReleaseQuery;
+use crate::query::update_release_asset::UpdateReleaseAssetQuery;
+use crate::query::upload_release_asset::UploadReleaseAssetQuery;
+use crate::url_util::{UriTemplate, UrlExt};
+use crate::GithubAuth;
+use bytes::Bytes;
+use chrono::{Duration, Utc};
+use compact_str::{format_compact, CompactString};
+use core::task::{Context, Poll};
+use futures::FutureExt;
+use sha2::{Digest as _, Sha256};
+use std::collections::{BTreeMap, VecDeque};
+use std::future::{ready, Ready};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard};
+use tower_service::Service;
+use url::Url;
+
+/// Default number of items per page, same as GitHub
+const DEFAULT_PER_PAGE: usize = 30;
+
+/// In-memory stand-in for GitHub, implementing the services for all the queries.
+///
+/// It is intended for tests of code depending on a [`GithubClient`](crate::GithubClient): repositories and releases
+/// can be seeded directly or from JSON fixtures, lists are paginated with `Link`-style cursors, and errors can be
+/// injected with [`FakeGithub::push_error`].
+///
+/// Clones share the same state.
+#[derive(Debug, Clone)]
+pub struct FakeGithub {
+  state: Arc<Mutex<FakeState>>,
+}
+
+#[derive(Debug)]
+struct FakeState {
+  base: Url,
+  repositories: BTreeMap<ProjectId, FakeRepository>,
+  /// Content of the uploaded assets, by asset id
+  asset_contents: BTreeMap<ReleaseAssetId, Bytes>,
+  /// Account attributed as the author of created releases and the uploader of uploaded assets
+  user: SimpleUser,
+  errors: VecDeque<HttpGithubClientError>,
+  next_id: u64,
+}
+
+#[derive(Debug)]
+struct FakeRepository {
+  owner: CompactString,
+  name: CompactString,
+  releases: Vec<Release>,
+}
+
+impl Default for FakeGithub {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl FakeGithub {
+  /// Create an empty fake, using `https://api.github.com/` as its base URL.
+  pub fn new() -> Self {
+    Self::with_base(Url::parse("https://api.github.com/").expect("base URL is valid"))
+  }
+
+  pub fn with_base(base: Url) -> Self {
+    let user = SimpleUser {
+      login: CompactString::new("octocat"),
+      id: UserId::new(1),
+      node_id: CompactString::new("MDQ6VXNlcjE="),
+      avatar_url: Url::parse("https://github.com/images/error/octocat_happy.gif").expect("avatar URL is valid"),
+      gravatar_id: None,
+      url: base.url_join(["users", "octocat"]),
+      html_url: Url::parse("https://github.com/octocat").expect("profile URL is valid"),
+      r#type: CompactString::new("User"),
+      site_admin: false,
+    };
+    Self {
+      state: Arc::new(Mutex::new(FakeState {
+        base,
+        repositories: BTreeMap::new(),
+        asset_contents: BTreeMap::new(),
+        user,
+        errors: VecDeque::new(),
+        next_id: 1,
+      })),
+    }
+  }
+
+  fn state(&self) -> MutexGuard<'_, FakeState> {
+    self.state.lock().expect("fake state is not poisoned")
+  }
+
+  /// Add an empty repository, returning its id.
+  pub fn add_repository(&self, owner: &str, name: &str) -> ProjectId {
+    let mut state = self.state();
+    let id = ProjectId::new(state.next_id());
+    state.repositories.insert(
+      id,
+      FakeRepository {
+        owner: CompactString::new(owner),
+        name: CompactString::new(name),
+        releases: Vec::new(),
+      },
+    );
+    id
+  }
+
+  /// Add a release to the repository `repository`.
+  ///
+  /// Panics if the repository does not exist.
+  pub fn add_release(&self, repository: ProjectId, release: Release) {
+    let mut state = self.state();
+    state.next_id = state.next_id.max(release.id.into_u64() + 1);
+    state
+      .repositories
+      .get_mut(&repository)
+      .expect("repository exists")
+      .releases
+      .push(release);
+  }
+
+  /// Add a repository with the releases from a JSON fixture (the body of a "list releases" response).
+  pub fn load_releases(&self, owner: &str, name: &str, path: impl AsRef<Path>) -> std::io::Result<ProjectId> {
+    let raw = std::fs::read(path)?;
+    let releases: Vec<Release> = serde_json::from_slice(&raw)?;
+    let id = self.add_repository(owner, name);
+    for release in releases {
+      self.add_release(id, release);
+    }
+    Ok(id)
+  }
+
+  /// Publish a release of `repository` for the tag `tag`, with the assets `assets` (file name and content).
+  ///
+  /// The release and its assets are created with [`CreateReleaseQuery`] and [`UploadReleaseAssetQuery`], so assets
+  /// get a digest. Returns the published release. Panics if a query fails, e.g. if the tag is already used.
+  pub fn publish_release(&self, repository: ProjectId, tag: &str, assets: &[(&str, &[u8])]) -> Release {
+    let mut client = self.clone();
+    let auth = Some(GithubAuth::Bearer("ghp_fake"));
+    let mut query = CreateReleaseQuery::new(RepositoryRef::Id(repository), InputRelease::new(tag));
+    query.auth = auth;
+    let release = client
+      .call(&query)
+      .now_or_never()
+      .expect("fake queries are ready")
+      .expect("release is created");
+    for (name, content) in assets {
+      let mut upload = UploadReleaseAssetQuery::new(
+        release.upload_url.clone(),
+        *name,
+        "application/octet-stream",
+        Bytes::copy_from_slice(content),
+      );
+      upload.auth = auth;
+      client
+        .call(&upload)
+        .now_or_never()
+        .expect("fake queries are ready")
+        .expect("asset is uploaded");
+    }
+    self
+      .releases(repository)
+      .into_iter()
+      .find(|published| published.id == release.id)
+      .expect("release is published")
+  }
+
+  /// Releases of a repository, newest first.
+  pub fn releases(&self, repository: ProjectId) -> Vec<Release> {
+    self
+      .state()
+      .repositories
+      .get(&repository)
+      .map(FakeRepository::sorted_releases)
+      .unwrap_or_default()
+  }
+
+  /// Content of an asset, uploaded with [`UploadReleaseAssetQuery`] or set with [`FakeGithub::set_asset_content`]
+  pub fn asset_content(&self, asset: ReleaseAssetId) -> Option<Bytes> {
+    self.state().asset_contents.get(&asset).cloned()
+  }
+
+  /// Set the content served when downloading the asset `asset`, e.g. for assets loaded from fixtures.
+  pub fn set_asset_content(&self, asset: ReleaseAssetId, content: Bytes) {
+    self.state().asset_contents.insert(asset, content);
+  }
+
+  /// Set the account attributed as the author of created releases and the uploader of uploaded assets.
+  ///
+  /// Defaults to `octocat`.
+  pub fn set_user(&self, user: SimpleUser) {
+    self.state().user = user;
+  }
+
+  /// Make the next query fail with `error`.
+  ///
+  /// Errors are consumed in the order they were pushed, one per query.
+  pub fn push_error(&self, error: HttpGithubClientError) {
+    self.state().errors.push_back(error);
+  }
+}
+
+impl FakeState {
+  fn next_id(&mut self) -> u64 {
+    let id = self.next_id;
+    self.next_id += 1;
+    id
+  }
+
+  fn take_error(&mut self) -> Result<(), HttpGithubClientError> {
+    match self.errors.pop_front() {
+      Some(e) => Err(e),
+      None => Ok(()),
+    }
+  }
+
+  fn resolve<Str: AsRef<str>>(&self, repository: &RepositoryRef<Str>) -> Result<ProjectId, HttpGithubClientError> {
+    match repository.as_view() {
+      RepositoryRef::Id(id) => self.repositories.contains_key(&id).then_some(id),
+      RepositoryRef::Slug(slug) => self
+        .repositories
+        .iter()
+        .find(|(_, repo)| repo.owner.eq_ignore_ascii_case(slug.owner) && repo.name.eq_ignore_ascii_case(slug.name))
+        .map(|(id, _)| *id),
+    }
+    .ok_or(HttpGithubClientError::NotFound(None))
+  }
+
+  /// First release of the repository (newest first) matching `predicate`
+  fn find_release<Str: AsRef<str>>(
+    &self,
+    repository: &RepositoryRef<Str>,
+    predicate: impl Fn(&Release) -> bool,
+  ) -> Result<Release, HttpGithubClientError> {
+    let repository = self.resolve(repository)?;
+    self.repositories[&repository]
+      .sorted_releases()
+      .into_iter()
+      .find(predicate)
+      .ok_or(HttpGithubClientError::NotFound(None))
+  }
+
+  /// Release with the id `release` in the repository, for modification
+  fn release_mut<Str: AsRef<str>>(
+    &mut self,
+    repository: &RepositoryRef<Str>,
+    release: ReleaseId,
+  ) -> Result<&mut Release, HttpGithubClientError> {
+    let repository = self.resolve(repository)?;
+    self
+      .repositories
+      .get_mut(&repository)
+      .expect("resolved repository exists")
+      .releases
+      .iter_mut()
+      .find(|r| r.id == release)
+      .ok_or(HttpGithubClientError::NotFound(None))
+  }
+
+  /// Asset with the id `asset` in any release of the repository, for modification
+  fn asset_mut<Str: AsRef<str>>(
+    &mut self,
+    repository: &RepositoryRef<Str>,
+    asset: ReleaseAssetId,
+  ) -> Result<&mut ReleaseAsset, HttpGithubClientError> {
+    let repository = self.resolve(repository)?;
+    self
+      .repositories
+      .get_mut(&repository)
+      .expect("resolved repository exists")
+      .releases
+      .iter_mut()
+      .flat_map(|release| release.assets.iter_mut())
+      .find(|a| a.id == asset)
+      .ok_or(HttpGithubClientError::NotFound(None))
+  }
+
+  /// Fail with the error returned by GitHub if the tag is already used by another release
+  fn check_tag_available(
+    &self,
+    repository: ProjectId,
+    tag: &str,
+    release: Option<ReleaseId>,
+  ) -> Result<(), HttpGithubClientError> {
+    let taken = self.repositories[&repository]
+      .releases
+      .iter()
+      .any(|r| r.tag_name == tag && Some(r.id) != release);
+    if taken {
+      return Err(HttpGithubClientError::Conflict(Some(GithubErrorBody {
+        message: CompactString::new("Validation Failed"),
+        documentation_url: None,
+        status: Some(CompactString::new("422")),
+        errors: vec![GithubValidationError {
+          resource: Some(CompactString::new("Release")),
+          field: Some(CompactString::new("tag_name")),
+          code: CompactString::new(GithubValidationError::ALREADY_EXISTS),
+          message: None,
+        }],
+      })));
+    }
+    Ok(())
+  }
+
+  fn create_release(
+    &mut self,
+    repository: ProjectId,
+    input: InputReleaseView<'_>,
+  ) -> Result<Release, HttpGithubClientError> {
+    self.check_tag_available(repository, input.tag_name, None)?;
+    let id = ReleaseId::new(self.next_id());
+    let repo = &self.repositories[&repository];
+    let api = self.base.url_join(["repos", repo.owner.as_str(), repo.name.as_str()]);
+    let url = |segments: &[&str]| api.url_join(segments);
+    let draft = input.draft.unwrap_or(false);
+    let now = Utc::now();
+    let release = Release {
+      url: id.with_str(|id| url(&["releases", id])),
+      html_url: Url::parse("https://github.com/")
+        .expect("GitHub URL is valid")
+        .url_join([
+          repo.owner.as_str(),
+          repo.name.as_str(),
+          "releases",
+          "tag",
+          input.tag_name,
+        ]),
+      assets_url: id.with_str(|id| url(&["releases", id, "assets"])),
+      upload_url: UriTemplate::new(format!(
+        "https://uploads.github.com/repos/{}/{}/releases/{}/assets{{?name,label}}",
+        repo.owner,
+        repo.name,
+        id.into_u64()
+      )),
+      tarball_url: Some(url(&["tarball", input.tag_name])),
+      zipball_url: Some(url(&["zipball", input.tag_name])),
+      id,
+      node_id: format!("RE_fake{}", id.into_u64()),
+      tag_name: input.tag_name.to_string(),
+      target_commitish: input.target_commitish.unwrap_or("main").to_string(),
+      name: input.name.map(String::from),
+      body: input.body.map(String::from),
+      draft,
+      prerelease: input.prerelease.unwrap_or(false),
+      created_at: now,
+      published_at: (!draft).then_some(now),
+      author: self.user.clone(),
+      assets: Vec::new(),
+      reactions: None,
+      mentions_count: None,
+    };
+    self
+      .repositories
+      .get_mut(&repository)
+      .expect("repository exists")
+      .releases
+      .push(release.clone());
+    Ok(release)
+  }
+
+  /// Build a page of the release list, with cursors in the format used by GitHub `Link` headers.
+  fn release_page(&self, repository: ProjectId, page: usize, per_page: usize) -> Page<Release> {
+    let list = repository.with_str(|id| self.base.url_join(["repositories", id, "releases"]));
+    paginate(&list, self.repositories[&repository].sorted_releases(), page, per_page)
+  }
+
+  /// Build a page of the asset list of a release
+  fn asset_page(
+    &self,
+    repository: ProjectId,
+    release: ReleaseId,
+    page: usize,
+    per_page: usize,
+  ) -> Result<Page<ReleaseAsset>, HttpGithubClientError> {
+    let assets = self.repositories[&repository]
+      .releases
+      .iter()
+      .find(|r| r.id == release)
+      .ok_or(HttpGithubClientError::NotFound(None))?
+      .assets
+      .clone();
+    let list = repository.with_str(|id| {
+      release.with_str(|release| self.base.url_join(["repositories", id, "releases", release, "assets"]))
+    });
+    Ok(paginate(&list, assets, page, per_page))
+  }
+
+  /// Read a page cursor, returning the repository, the path segments following it, and the page parameters
+  fn read_cursor(&self, cursor: &str) -> Result<PageCursor, HttpGithubClientError> {
+    let url = Url::parse(cursor)
+      .ok()
+      .filter(|url| url.origin() == self.base.origin())
+      .ok_or_else(|| HttpGithubClientError::UntrustedOrigin(cursor.to_string()))?;
+    let mut segments = url.path_segments().ok_or(HttpGithubClientError::NotFound(None))?;
+    let repository = match (segments.next(), segments.next()) {
+      (Some("repositories"), Some(id)) => id.parse().ok().map(ProjectId::new),
+      _ => None,
+    }
+    .ok_or(HttpGithubClientError::NotFound(None))?;
+    let path = segments.map(String::from).collect();
+    let repository = self.resolve(&RepositoryRef::<&str>::Id(repository))?;
+    let param = |name: &str| -> Option<usize> {
+      url
+        .query_pairs()
+        .find(|(key, _)| key == name)
+        .and_then(|(_, value)| value.parse().ok())
+    };
+    Ok(PageCursor {
+      repository,
+      path,
+      page: param("page").unwrap_or(1).max(1),
+      per_page: param("per_page").unwrap_or(DEFAULT_PER_PAGE).clamp(1, 100),
+    })
+  }
+}
+
+struct PageCursor {
+  repository: ProjectId,
+  path: Vec<String>,
+  page: usize,
+  per_page: usize,
+}
+
+/// Build a page of `items`, with cursors in the format used by GitHub `Link` headers.
+fn paginate<T>(list: &Url, items: Vec<T>, page: usize, per_page: usize) -> Page<T> {
+  let last = items.len().div_ceil(per_page).max(1);
+  let cursor = |page: usize| {
+    let mut url = list.clone();
+    url
+      .query_pairs_mut()
+      .append_pair("per_page", per_page.to_string().as_str())
+      .append_pair("page", page.to_string().as_str());
+    CompactString::from(url.as_str())
+  };
+  Page {
+    first: (page > 1).then(|| cursor(1)),
+    next: (page < last).then(|| cursor(page + 1)),
+    last: (page < last).then(|| cursor(last)),
+    rate_limit: None,
+    items: items.into_iter().skip((page - 1) * per_page).take(per_page).collect(),
+  }
+}
+
+impl FakeRepository {
+  fn sorted_releases(&self) -> Vec<Release> {
+    let mut releases = self.releases.clone();
+    releases.sort_by(|a, b| b.created_at.cmp(&a.created_at).then(b.id.cmp(&a.id)));
+    releases
+  }
+}
+
+impl<Cx, Str> Service<&'_ GetProjectReleaseListQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = Page<Release>;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &GetProjectReleaseListQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      let repository = state.resolve(&req.repository)?;
+      let per_page = req
+        .pagination
+        .and_then(|pagination| pagination.per_page)
+        .map_or(DEFAULT_PER_PAGE, |per_page| usize::from(per_page.get()));
+      Ok(state.release_page(repository, 1, per_page))
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ GetProjectReleaseListPageQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = Page<Release>;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &GetProjectReleaseListPageQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      let cursor = state.read_cursor(req.cursor.as_ref())?;
+      if cursor.path != ["releases"] {
+        return Err(HttpGithubClientError::NotFound(None));
+      }
+      Ok(state.release_page(cursor.repository, cursor.page, cursor.per_page))
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ GetProjectReleaseQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &GetProjectReleaseQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(
+      state
+        .take_error()
+        .and_then(|()| state.find_release(&req.repository, |release| release.id == req.release)),
+    )
+  }
+}
+
+impl<Cx, Str> Service<&'_ GetProjectReleaseByTagQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &GetProjectReleaseByTagQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(
+      state
+        .take_error()
+        .and_then(|()| state.find_release(&req.repository, |release| release.tag_name == req.tag.as_ref())),
+    )
+  }
+}
+
+impl<Cx, Str> Service<&'_ GetProjectLatestReleaseQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &GetProjectLatestReleaseQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(
+      state
+        .take_error()
+        .and_then(|()| state.find_release(&req.repository, |release| !release.draft && !release.prerelease)),
+    )
+  }
+}
+
+impl<Cx, Str> Service<&'_ CreateReleaseQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &CreateReleaseQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      if req.auth.is_none() {
+        return Err(HttpGithubClientError::Forbidden(None));
+      }
+      let repository = state.resolve(&req.repository)?;
+      state.create_release(repository, req.input.as_view())
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ UpdateReleaseQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = Release;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &UpdateReleaseQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      if req.auth.is_none() {
+        return Err(HttpGithubClientError::Forbidden(None));
+      }
+      let InputReleasePatch {
+        tag_name,
+        target_commitish,
+        name,
+        body,
+        draft,
+        prerelease,
+        make_latest: _,
+        discussion_category_name: _,
+      } = req.input.as_view();
+      if let Some(tag_name) = tag_name {
+        let repository = state.resolve(&req.repository)?;
+        state.check_tag_available(repository, tag_name, Some(req.release))?;
+      }
+      let release = state.release_mut(&req.repository, req.release)?;
+      if let Some(tag_name) = tag_name {
+        release.tag_name = tag_name.to_string();
+      }
+      if let Some(target_commitish) = target_commitish {
+        release.target_commitish = target_commitish.to_string();
+      }
+      if let Some(name) = name {
+        release.name = Some(name.to_string());
+      }
+      if let Some(body) = body {
+        release.body = Some(body.to_string());
+      }
+      if let Some(prerelease) = prerelease {
+        release.prerelease = prerelease;
+      }
+      if let Some(draft) = draft {
+        if release.draft && !draft {
+          release.published_at = Some(Utc::now());
+        }
+        release.draft = draft;
+      }
+      Ok(release.clone())
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ DeleteReleaseQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = ();
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &DeleteReleaseQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      if req.auth.is_none() {
+        return Err(HttpGithubClientError::Forbidden(None));
+      }
+      let repository = state.resolve(&req.repository)?;
+      let releases = &mut state
+        .repositories
+        .get_mut(&repository)
+        .expect("resolved repository exists")
+        .releases;
+      let index = releases
+        .iter()
+        .position(|release| release.id == req.release)
+        .ok_or(HttpGithubClientError::NotFound(None))?;
+      releases.remove(index);
+      Ok(())
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ UploadReleaseAssetQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = ReleaseAsset;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &UploadReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      if req.auth.is_none() {
+        return Err(HttpGithubClientError::Forbidden(None));
+      }
+      let url = req
+        .upload_url
+        .expand(&[("name", Some(req.name.as_ref()))])
+        .map_err(|e| HttpGithubClientError::Other(format!("{e:?}")))?;
+      let (owner, name, release) = url
+        .path_segments()
+        .and_then(|mut segments| {
+          match (
+            segments.next(),
+            segments.next(),
+            segments.next(),
+            segments.next(),
+            segments.next(),
+          ) {
+            (Some("repos"), Some(owner), Some(name), Some("releases"), Some(release)) => {
+              Some((owner, name, ReleaseId::new(release.parse().ok()?)))
+            }
+            _ => None,
+          }
+        })
+        .ok_or(HttpGithubClientError::NotFound(None))?;
+      let repository = RepositoryRef::Slug(ProjectSlug::new(owner, name));
+      let id = ReleaseAssetId::new(state.next_id());
+      let base = state.base.clone();
+      let user = state.user.clone();
+      let release = state.release_mut(&repository, release)?;
+      if release.assets.iter().any(|asset| asset.name == req.name.as_ref()) {
+        return Err(HttpGithubClientError::Conflict(None));
+      }
+      let now = Utc::now();
+      let asset = ReleaseAsset {
+        url: id.with_str(|id| base.url_join(["repos", owner, name, "releases", "assets", id])),
+        browser_download_url: Url::parse("https://github.com/")
+          .expect("GitHub URL is valid")
+          .url_join([
+            owner,
+            name,
+            "releases",
+            "download",
+            release.tag_name.as_str(),
+            req.name.as_ref(),
+          ]),
+        id,
+        node_id: format!("RA_fake{}", id.into_u64()),
+        name: req.name.as_ref().to_string(),
+        label: req.label.as_ref().map(|label| label.as_ref().to_string()),
+        state: String::from("uploaded"),
+        content_type: req.content_type.as_ref().to_string(),
+        size: req.content.len() as u64,
+        digest: Some(Digest::Sha256(Sha256::digest(&req.content).into())),
+        download_count: 0,
+        created_at: now,
+        updated_at: now,
+        uploader: Some(user),
+      };
+      release.assets.push(asset.clone());
+      state.asset_contents.insert(id, req.content.clone());
+      Ok(asset)
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ DownloadReleaseAssetQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = ReleaseAssetDownload;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &DownloadReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      let repository = state.resolve(&req.repository)?;
+      let asset = state.repositories[&repository]
+        .releases
+        .iter()
+        .flat_map(|release| release.assets.iter())
+        .find(|asset| asset.id == req.asset)
+        .ok_or(HttpGithubClientError::NotFound(None))?;
+      let content = state
+        .asset_contents
+        .get(&asset.id)
+        .cloned()
+        .ok_or(HttpGithubClientError::NotFound(None))?;
+      let size = content.len() as u64;
+      let offset = req.offset.unwrap_or(0);
+      if offset > size {
+        return Err(HttpGithubClientError::Status(416, None));
+      }
+      let chunk = content.slice(usize::try_from(offset).expect("offset is within the content")..);
+      Ok(ReleaseAssetDownload {
+        offset,
+        size: Some(size),
+        content_type: Some(CompactString::new(&asset.content_type)),
+        content: Box::pin(futures::stream::iter([Ok(chunk)])),
+      })
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ GetReleaseAssetListQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = Page<ReleaseAsset>;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &GetReleaseAssetListQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      let repository = state.resolve(&req.repository)?;
+      let per_page = req
+        .pagination
+        .and_then(|pagination| pagination.per_page)
+        .map_or(DEFAULT_PER_PAGE, |per_page| usize::from(per_page.get()));
+      state.asset_page(repository, req.release, 1, per_page)
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ GetReleaseAssetListPageQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = Page<ReleaseAsset>;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &GetReleaseAssetListPageQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      let cursor = state.read_cursor(req.cursor.as_ref())?;
+      let release = match cursor.path.as_slice() {
+        [releases, release, assets] if releases == "releases" && assets == "assets" => {
+          release.parse().ok().map(ReleaseId::new)
+        }
+        _ => None,
+      }
+      .ok_or(HttpGithubClientError::NotFound(None))?;
+      state.asset_page(cursor.repository, release, cursor.page, cursor.per_page)
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ GetReleaseAssetQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = ReleaseAsset;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &GetReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(
+      state
+        .take_error()
+        .and_then(|()| state.asset_mut(&req.repository, req.asset).map(|asset| asset.clone())),
+    )
+  }
+}
+
+impl<Cx, Str> Service<&'_ UpdateReleaseAssetQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = ReleaseAsset;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &UpdateReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      if req.auth.is_none() {
+        return Err(HttpGithubClientError::Forbidden(None));
+      }
+      let InputReleaseAssetPatch {
+        name,
+        label,
+        state: asset_state,
+      } = req.input.as_view();
+      let asset = state.asset_mut(&req.repository, req.asset)?;
+      if let Some(name) = name {
+        asset.name = name.to_string();
+      }
+      if let Some(label) = label {
+        asset.label = Some(label.to_string());
+      }
+      if let Some(asset_state) = asset_state {
+        asset.state = asset_state.to_string();
+      }
+      asset.updated_at = Utc::now();
+      Ok(asset.clone())
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ DeleteReleaseAssetQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = ();
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &DeleteReleaseAssetQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      if req.auth.is_none() {
+        return Err(HttpGithubClientError::Forbidden(None));
+      }
+      let repository = state.resolve(&req.repository)?;
+      let deleted = state
+        .repositories
+        .get_mut(&repository)
+        .expect("resolved repository exists")
+        .releases
+        .iter_mut()
+        .any(|release| {
+          let len = release.assets.len();
+          release.assets.retain(|asset| asset.id != req.asset);
+          release.assets.len() != len
+        });
+      if !deleted {
+        return Err(HttpGithubClientError::NotFound(None));
+      }
+      state.asset_contents.remove(&req.asset);
+      Ok(())
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ GenerateReleaseNotesQuery<Cx, Str>> for FakeGithub
+where
+  Str: AsRef<str>,
+{
+  type Response = ReleaseNotes;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  /// Only the "Full Changelog" link is generated, from the previous published release by default.
+  fn call(&mut self, req: &GenerateReleaseNotesQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      if req.auth.is_none() {
+        return Err(HttpGithubClientError::Forbidden(None));
+      }
+      let input = req.input.as_view();
+      let repository = state.resolve(&req.repository)?;
+      let repo = &state.repositories[&repository];
+      let releases = repo.sorted_releases();
+      let previous = match input.previous_tag_name {
+        Some(previous) => Some(
+          releases
+            .iter()
+            .find(|release| release.tag_name == previous)
+            .ok_or(HttpGithubClientError::NotFound(None))?,
+        ),
+        None => releases
+          .iter()
+          .find(|release| !release.draft && release.tag_name != input.tag_name),
+      };
+      let changes = match previous {
+        Some(previous) => format!("compare/{}...{}", previous.tag_name, input.tag_name),
+        None => format!("commits/{}", input.tag_name),
+      };
+      Ok(ReleaseNotes {
+        name: input.tag_name.to_string(),
+        body: format!(
+          "**Full Changelog**: https://github.com/{}/{}/{changes}",
+          repo.owner, repo.name
+        ),
+      })
+    }))
+  }
+}
+
+impl<Cx, Str> Service<&'_ CreateInstallationAccessTokenQuery<Cx, Str>> for FakeGithub {
+  type Response = InstallationAccessToken;
+  type Error = HttpGithubClientError;
+  type Future = Ready<Result<Self::Response, Self::Error>>;
+
+  fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+    Poll::Ready(Ok(()))
+  }
+
+  fn call(&mut self, req: &CreateInstallationAccessTokenQuery<Cx, Str>) -> Self::Future {
+    let mut state = self.state();
+    ready(state.take_error().and_then(|()| {
+      if req.auth.is_none() {
+        return Err(HttpGithubClientError::Forbidden(None));
+      }
+      let token = req
+        .installation
+        .with_str(|installation| format_compact!("ghs_fake_{installation}_{}", state.next_id()));
+      Ok(InstallationAccessToken {
+        token,
+        expires_at: Utc::now() + Duration::hours(1),
+        permissions: None,
+        repository_selection: None,
+      })
+    }))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::common::project::ProjectSlug;
+  use crate::common::release::{InputRelease, InputReleaseNotes};
+  use crate::common::Pagination;
+  use crate::paginate::Paginate;
+  use crate::{GithubAuth, GithubClient};
+  use futures::TryStreamExt;
+  use std::num::NonZeroU8;
+
+  fn hello_world() -> (FakeGithub, ProjectId) {
+    let github = FakeGithub::new();
+    let id = github
+      .load_releases(
+        "octocat",
+        "Hello-World",
+        "./test-resources/fake/octocat-hello-world/releases.json",
+      )
+      .unwrap();
+    (github, id)
+  }
+
+  #[tokio::test]
+  async fn paginate_fixture_releases() {
+    let (mut github, _) = hello_world();
+    let mut query = GetProjectReleaseListQuery::new(RepositoryRef::Slug(ProjectSlug::new(
+      CompactString::new("octocat"),
+      CompactString::new("hello-world"),
+    )));
+    query.pagination = Some(Pagination {
+      per_page: NonZeroU8::new(3),
+    });
+
+    let first = (&mut github).get_project_release_list(&query).await.unwrap();
+    assert_eq!(
+      first.next.as_deref(),
+      Some("https://api.github.com/repositories/1/releases?per_page=3&page=2")
+    );
+
+    let tags: Vec<String> = Paginate::new(&mut github, query)
+      .items()
+      .map_ok(|release| release.tag_name)
+      .try_collect()
+      .await
+      .unwrap();
+    assert_eq!(tags, ["v1.2.0", "v1.1.0", "v1.1.0-rc.1", "v1.0.0"]);
+  }
+
+  #[tokio::test]
+  async fn inject_errors() {
+    let (mut github, id) = hello_world();
+    github.push_error(HttpGithubClientError::Forbidden(None));
+    let query = GetProjectReleaseListQuery::<_, CompactString>::new(RepositoryRef::Id(id));
+    assert_eq!(
+      (&mut github).get_project_release_list(&query).await,
+      Err(HttpGithubClientError::Forbidden(None))
+    );
+    assert!((&mut github).get_project_release_list(&query).await.is_ok());
+
+    let missing = GetProjectReleaseListQuery::<_, CompactString>::new(RepositoryRef::Id(ProjectId::new(999)));
+    assert_eq!(
+      (&mut github).get_project_release_list(&missing).await,
+      Err(HttpGithubClientError::NotFound(None))
+    );
+  }
+
+  #[tokio::test]
+  async fn get_single_releases() {
+    let (mut github, id) = hello_world();
+    let repository = RepositoryRef::<CompactString>::Id(id);
+
+    let query = GetProjectReleaseQuery::new(repository.clone(), ReleaseId::new(1002));
+    let release = (&mut github).get_project_release(&query).await.unwrap();
+    assert_eq!(release.tag_name, "v1.1.0-rc.1");
+
+    let query = GetProjectReleaseByTagQuery::new(repository.clone(), CompactString::new("v1.1.0"));
+    let release = (&mut github).get_project_release_by_tag(&query).await.unwrap();
+    assert_eq!(release.id, ReleaseId::new(1003));
+
+    let query = GetProjectLatestReleaseQuery::new(repository.clone());
+    let release = (&mut github).get_project_latest_release(&query).await.unwrap();
+    assert_eq!(release.tag_name, "v1.2.0");
+    assert_eq!(release.author.login, "octocat");
+    assert_eq!(release.reactions.map(|reactions| reactions.plus_one), Some(2));
+    assert_eq!(release.mentions_count, Some(1));
+
+    let query = GetProjectReleaseByTagQuery::new(repository, CompactString::new("v9.9.9"));
+    assert_eq!(
+      (&mut github).get_project_release_by_tag(&query).await,
+      Err(HttpGithubClientError::NotFound(None))
+    );
+  }
+
+  #[tokio::test]
+  async fn create_update_and_delete_release() {
+    let (mut github, id) = hello_world();
+    let repository = RepositoryRef::<CompactString>::Id(id);
+    let auth = Some(GithubAuth::Bearer(CompactString::new("ghp_fake")));
+
+    let mut input = InputRelease::new(CompactString::new("v2.0.0"));
+    input.draft = Some(true);
+    let mut query = CreateReleaseQuery::new(repository.clone(), input);
+    query.auth = auth.clone();
+    let created = (&mut github).create_release(&query).await.unwrap();
+    assert!(created.draft);
+    assert_eq!(created.published_at, None);
+    assert!(matches!(
+      (&mut github).create_release(&query).await,
+      Err(HttpGithubClientError::Conflict(Some(_)))
+    ));
+
+    let patch = InputReleasePatch {
+      draft: Some(false),
+      name: Some(CompactString::new("Hello World v2.0.0")),
+      ..InputReleasePatch::default()
+    };
+    let mut query = UpdateReleaseQuery::new(repository.clone(), created.id, patch);
+    query.auth = auth.clone();
+    let updated = (&mut github).update_release(&query).await.unwrap();
+    assert!(!updated.draft);
+    assert!(updated.published_at.is_some());
+    assert_eq!(github.releases(id)[0].name.as_deref(), Some("Hello World v2.0.0"));
+
+    let mut query = DeleteReleaseQuery::new(repository, created.id);
+    query.auth = auth;
+    (&mut github).delete_release(&query).await.unwrap();
+    assert_eq!(github.releases(id).len(), 4);
+    assert_eq!(
+      (&mut github).delete_release(&query).await,
+      Err(HttpGithubClientError::NotFound(None))
+    );
+  }
+
+  #[tokio::test]
+  async fn upload_release_asset() {
+    let (mut github, id) = hello_world();
+    let release = github.releases(id).remove(0);
+    let mut query = UploadReleaseAssetQuery::new(
+      release.upload_url,
+      CompactString::new("SHA256SUMS"),
+      CompactString::new("text/plain"),
+      Bytes::from_static(b"0123abcd  hello.tar.gz\n"),
+    );
+    query.auth = Some(GithubAuth::Bearer(CompactString::new("ghp_fake")));
+
+    let asset = (&mut github).upload_release_asset(&query).await.unwrap();
+    assert_eq!(asset.size, 23);
+    assert_eq!(
+      asset.uploader.map(|uploader| uploader.login).as_deref(),
+      Some("octocat")
+    );
+    assert_eq!(
+      asset.browser_download_url.as_str(),
+      "https://github.com/octocat/Hello-World/releases/download/v1.2.0/SHA256SUMS"
+    );
+    assert_eq!(github.releases(id)[0].assets.len(), 3);
+    assert_eq!(github.asset_content(asset.id), Some(query.content.clone()));
+    assert_eq!(
+      (&mut github).upload_release_asset(&query).await,
+      Err(HttpGithubClientError::Conflict(None))
+    );
+  }
+
+  #[tokio::test]
+  async fn download_release_asset() {
+    let (mut github, id) = hello_world();
+    github.set_asset_content(ReleaseAssetId::new(5011), Bytes::from_static(b"hello"));
+    let mut query =
+      DownloadReleaseAssetQuery::<_, CompactString>::new(RepositoryRef::Id(id), ReleaseAssetId::new(5011));
+    query.offset = Some(1);
+
+    let download = (&mut github).download_release_asset(&query).await.unwrap();
+    assert_eq!((download.offset, download.size), (1, Some(5)));
+    let content: Vec<Bytes> = download.content.try_collect().await.unwrap();
+    assert_eq!(content.concat(), b"ello");
+
+    query.asset = ReleaseAssetId::new(5012);
+    assert!(matches!(
+      (&mut github).download_release_asset(&query).await,
+      Err(HttpGithubClientError::NotFound(None))
+    ));
+  }
+
+  #[tokio::test]
+  async fn list_update_and_delete_release_assets() {
+    let (mut github, id) = hello_world();
+    let repository = RepositoryRef::<CompactString>::Id(id);
+    let auth = Some(GithubAuth::Bearer(CompactString::new("ghp_fake")));
+
+    let mut query = GetReleaseAssetListQuery::new(repository.clone(), ReleaseId::new(1004));
+    query.pagination = Some(Pagination {
+      per_page: NonZeroU8::new(1),
+    });
+    let names: Vec<String> = Paginate::new(&mut github, query)
+      .items()
+      .map_ok(|asset| asset.name)
+      .try_collect()
+      .await
+      .unwrap();
+    assert_eq!(names.len(), 2);
+
+    let patch = InputReleaseAssetPatch {
+      label: Some(CompactString::new("Linux x86_64")),
+      ..InputReleaseAssetPatch::default()
+    };
+    let mut query = UpdateReleaseAssetQuery::new(repository.clone(), ReleaseAssetId::new(5011), patch);
+    query.auth = auth.clone();
+    let updated = (&mut github).update_release_asset(&query).await.unwrap();
+    assert_eq!(updated.label.as_deref(), Some("Linux x86_64"));
+    let query = GetReleaseAssetQuery::new(repository.clone(), ReleaseAssetId::new(5011));
+    assert_eq!((&mut github).get_release_asset(&query).await, Ok(updated));
+
+    let mut query = DeleteReleaseAssetQuery::new(repository.clone(), ReleaseAssetId::new(5011));
+    query.auth = auth;
+    (&mut github).delete_release_asset(&query).await.unwrap();
+    assert_eq!(
+      (&mut github).delete_release_asset(&query).await,
+      Err(HttpGithubClientError::NotFound(None))
+    );
+    let query = GetReleaseAssetQuery::new(repository, ReleaseAssetId::new(5011));
+    assert_eq!(
+      (&mut github).get_release_asset(&query).await,
+      Err(HttpGithubClientError::NotFound(None))
+    );
+  }
+
+  #[tokio::test]
+  async fn generate_release_notes() {
+    let (mut github, id) = hello_world();
+    let mut query = GenerateReleaseNotesQuery::new(
+      RepositoryRef::<CompactString>::Id(id),
+      InputReleaseNotes::new(CompactString::new("v1.3.0")),
+    );
+    query.auth = Some(GithubAuth::Bearer(CompactString::new("ghp_fake")));
+    let notes = (&mut github).generate_release_notes(&query).await.unwrap();
+    assert_eq!(notes.name, "v1.3.0");
+    assert_eq!(
+      notes.body,
+      "**Full Changelog**: https://github.com/octocat/Hello-World/compare/v1.2.0...v1.3.0"
+    );
+  }
+}
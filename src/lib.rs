@@ -4,26 +4,59 @@ pub use ::compact_str;
 pub use ::demurgos_headers::UserAgent;
 #[cfg(feature = "reqwest")]
 pub use ::reqwest;
+#[cfg(feature = "semver")]
+pub use ::semver;
 #[cfg(feature = "serde")]
 pub use ::serde;
+pub use ::tower_layer;
 pub use ::tower_service;
 pub use ::url;
 use compact_str::CompactString;
 use std::future::Future;
 
-use crate::common::release::Release;
+use crate::common::app::InstallationAccessToken;
+use crate::common::release::{Release, ReleaseAsset, ReleaseAssetDownload, ReleaseNotes};
 use crate::common::Page;
+use crate::query::create_installation_access_token::CreateInstallationAccessTokenQuery;
+use crate::query::create_release::CreateReleaseQuery;
+use crate::query::delete_release::DeleteReleaseQuery;
+use crate::query::delete_release_asset::DeleteReleaseAssetQuery;
+use crate::query::download_release_asset::DownloadReleaseAssetQuery;
+use crate::query::generate_release_notes::GenerateReleaseNotesQuery;
+use crate::query::get_project_latest_release::GetProjectLatestReleaseQuery;
+use crate::query::get_project_release::GetProjectReleaseQuery;
+use crate::query::get_project_release_by_tag::GetProjectReleaseByTagQuery;
 use crate::query::get_project_release_list::GetProjectReleaseListQuery;
 use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
+use crate::query::get_release_asset::GetReleaseAssetQuery;
+use crate::query::get_release_asset_list::GetReleaseAssetListQuery;
+use crate::query::get_release_asset_list_page::GetReleaseAssetListPageQuery;
+use crate::query::update_release::UpdateReleaseQuery;
+use crate::query::update_release_asset::UpdateReleaseAssetQuery;
+use crate::query::upload_release_asset::UploadReleaseAssetQuery;
 use tower_service::Service;
 
+#[cfg(feature = "app")]
+pub mod app;
 pub mod client;
 pub mod common;
 pub mod context;
+#[cfg(feature = "fake")]
+pub mod fake;
 #[cfg(feature = "http")]
 pub mod http;
+#[cfg(feature = "mirror")]
+pub mod mirror;
+pub mod paginate;
+pub mod platform;
 pub mod query;
+#[cfg(feature = "semver")]
+pub mod resolve;
+#[cfg(feature = "self_update")]
+pub mod self_update;
 pub mod url_util;
+#[cfg(feature = "verify")]
+pub mod verify;
 
 pub trait GithubClient<Cx, Str>: Send + Sync {
   type GetProjectReleaseListError<'req>
@@ -45,6 +78,156 @@ pub trait GithubClient<Cx, Str>: Send + Sync {
     self,
     query: &GetProjectReleaseListPageQuery<Cx, Str>,
   ) -> impl Send + Future<Output = Result<Page<Release>, Self::GetProjectReleaseListPageError<'_>>>;
+
+  type GetProjectReleaseError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn get_project_release(
+    self,
+    query: &GetProjectReleaseQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<Release, Self::GetProjectReleaseError<'_>>>;
+
+  type GetProjectReleaseByTagError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn get_project_release_by_tag(
+    self,
+    query: &GetProjectReleaseByTagQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<Release, Self::GetProjectReleaseByTagError<'_>>>;
+
+  type GetProjectLatestReleaseError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn get_project_latest_release(
+    self,
+    query: &GetProjectLatestReleaseQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<Release, Self::GetProjectLatestReleaseError<'_>>>;
+
+  type CreateReleaseError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn create_release(
+    self,
+    query: &CreateReleaseQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<Release, Self::CreateReleaseError<'_>>>;
+
+  type UpdateReleaseError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn update_release(
+    self,
+    query: &UpdateReleaseQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<Release, Self::UpdateReleaseError<'_>>>;
+
+  type DeleteReleaseError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn delete_release(
+    self,
+    query: &DeleteReleaseQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<(), Self::DeleteReleaseError<'_>>>;
+
+  type UploadReleaseAssetError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn upload_release_asset(
+    self,
+    query: &UploadReleaseAssetQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<ReleaseAsset, Self::UploadReleaseAssetError<'_>>>;
+
+  type DownloadReleaseAssetError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn download_release_asset(
+    self,
+    query: &DownloadReleaseAssetQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<ReleaseAssetDownload, Self::DownloadReleaseAssetError<'_>>>;
+
+  type GetReleaseAssetListError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn get_release_asset_list(
+    self,
+    query: &GetReleaseAssetListQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<Page<ReleaseAsset>, Self::GetReleaseAssetListError<'_>>>;
+
+  type GetReleaseAssetListPageError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn get_release_asset_list_page(
+    self,
+    query: &GetReleaseAssetListPageQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<Page<ReleaseAsset>, Self::GetReleaseAssetListPageError<'_>>>;
+
+  type GetReleaseAssetError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn get_release_asset(
+    self,
+    query: &GetReleaseAssetQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<ReleaseAsset, Self::GetReleaseAssetError<'_>>>;
+
+  type UpdateReleaseAssetError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn update_release_asset(
+    self,
+    query: &UpdateReleaseAssetQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<ReleaseAsset, Self::UpdateReleaseAssetError<'_>>>;
+
+  type DeleteReleaseAssetError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn delete_release_asset(
+    self,
+    query: &DeleteReleaseAssetQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<(), Self::DeleteReleaseAssetError<'_>>>;
+
+  type GenerateReleaseNotesError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn generate_release_notes(
+    self,
+    query: &GenerateReleaseNotesQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<ReleaseNotes, Self::GenerateReleaseNotesError<'_>>>;
+
+  type CreateInstallationAccessTokenError<'req>
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  fn create_installation_access_token(
+    self,
+    query: &CreateInstallationAccessTokenQuery<Cx, Str>,
+  ) -> impl Send + Future<Output = Result<InstallationAccessToken, Self::CreateInstallationAccessTokenError<'_>>>;
 }
 
 impl<S, Cx, Str> GithubClient<Cx, Str> for &'_ mut S
@@ -54,6 +237,22 @@ where
   Str: Send + Sync,
   for<'req> S: Service<&'req GetProjectReleaseListQuery<Cx, Str>, Response = Page<Release>, Future: Send>,
   for<'req> S: Service<&'req GetProjectReleaseListPageQuery<Cx, Str>, Response = Page<Release>, Future: Send>,
+  for<'req> S: Service<&'req GetProjectReleaseQuery<Cx, Str>, Response = Release, Future: Send>,
+  for<'req> S: Service<&'req GetProjectReleaseByTagQuery<Cx, Str>, Response = Release, Future: Send>,
+  for<'req> S: Service<&'req GetProjectLatestReleaseQuery<Cx, Str>, Response = Release, Future: Send>,
+  for<'req> S: Service<&'req CreateReleaseQuery<Cx, Str>, Response = Release, Future: Send>,
+  for<'req> S: Service<&'req UpdateReleaseQuery<Cx, Str>, Response = Release, Future: Send>,
+  for<'req> S: Service<&'req DeleteReleaseQuery<Cx, Str>, Response = (), Future: Send>,
+  for<'req> S: Service<&'req UploadReleaseAssetQuery<Cx, Str>, Response = ReleaseAsset, Future: Send>,
+  for<'req> S: Service<&'req DownloadReleaseAssetQuery<Cx, Str>, Response = ReleaseAssetDownload, Future: Send>,
+  for<'req> S: Service<&'req GetReleaseAssetListQuery<Cx, Str>, Response = Page<ReleaseAsset>, Future: Send>,
+  for<'req> S: Service<&'req GetReleaseAssetListPageQuery<Cx, Str>, Response = Page<ReleaseAsset>, Future: Send>,
+  for<'req> S: Service<&'req GetReleaseAssetQuery<Cx, Str>, Response = ReleaseAsset, Future: Send>,
+  for<'req> S: Service<&'req UpdateReleaseAssetQuery<Cx, Str>, Response = ReleaseAsset, Future: Send>,
+  for<'req> S: Service<&'req DeleteReleaseAssetQuery<Cx, Str>, Response = (), Future: Send>,
+  for<'req> S: Service<&'req GenerateReleaseNotesQuery<Cx, Str>, Response = ReleaseNotes, Future: Send>,
+  for<'req> S:
+    Service<&'req CreateInstallationAccessTokenQuery<Cx, Str>, Response = InstallationAccessToken, Future: Send>,
 {
   type GetProjectReleaseListError<'req>
     = <S as Service<&'req GetProjectReleaseListQuery<Cx, Str>>>::Error
@@ -80,6 +279,192 @@ where
   ) -> Result<Page<Release>, Self::GetProjectReleaseListPageError<'_>> {
     self.call(query).await
   }
+
+  type GetProjectReleaseError<'req>
+    = <S as Service<&'req GetProjectReleaseQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn get_project_release(
+    self,
+    query: &GetProjectReleaseQuery<Cx, Str>,
+  ) -> Result<Release, Self::GetProjectReleaseError<'_>> {
+    self.call(query).await
+  }
+
+  type GetProjectReleaseByTagError<'req>
+    = <S as Service<&'req GetProjectReleaseByTagQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn get_project_release_by_tag(
+    self,
+    query: &GetProjectReleaseByTagQuery<Cx, Str>,
+  ) -> Result<Release, Self::GetProjectReleaseByTagError<'_>> {
+    self.call(query).await
+  }
+
+  type GetProjectLatestReleaseError<'req>
+    = <S as Service<&'req GetProjectLatestReleaseQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn get_project_latest_release(
+    self,
+    query: &GetProjectLatestReleaseQuery<Cx, Str>,
+  ) -> Result<Release, Self::GetProjectLatestReleaseError<'_>> {
+    self.call(query).await
+  }
+
+  type CreateReleaseError<'req>
+    = <S as Service<&'req CreateReleaseQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn create_release(self, query: &CreateReleaseQuery<Cx, Str>) -> Result<Release, Self::CreateReleaseError<'_>> {
+    self.call(query).await
+  }
+
+  type UpdateReleaseError<'req>
+    = <S as Service<&'req UpdateReleaseQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn update_release(self, query: &UpdateReleaseQuery<Cx, Str>) -> Result<Release, Self::UpdateReleaseError<'_>> {
+    self.call(query).await
+  }
+
+  type DeleteReleaseError<'req>
+    = <S as Service<&'req DeleteReleaseQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn delete_release(self, query: &DeleteReleaseQuery<Cx, Str>) -> Result<(), Self::DeleteReleaseError<'_>> {
+    self.call(query).await
+  }
+
+  type UploadReleaseAssetError<'req>
+    = <S as Service<&'req UploadReleaseAssetQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn upload_release_asset(
+    self,
+    query: &UploadReleaseAssetQuery<Cx, Str>,
+  ) -> Result<ReleaseAsset, Self::UploadReleaseAssetError<'_>> {
+    self.call(query).await
+  }
+
+  type DownloadReleaseAssetError<'req>
+    = <S as Service<&'req DownloadReleaseAssetQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn download_release_asset(
+    self,
+    query: &DownloadReleaseAssetQuery<Cx, Str>,
+  ) -> Result<ReleaseAssetDownload, Self::DownloadReleaseAssetError<'_>> {
+    self.call(query).await
+  }
+
+  type GetReleaseAssetListError<'req>
+    = <S as Service<&'req GetReleaseAssetListQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn get_release_asset_list(
+    self,
+    query: &GetReleaseAssetListQuery<Cx, Str>,
+  ) -> Result<Page<ReleaseAsset>, Self::GetReleaseAssetListError<'_>> {
+    self.call(query).await
+  }
+
+  type GetReleaseAssetListPageError<'req>
+    = <S as Service<&'req GetReleaseAssetListPageQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn get_release_asset_list_page(
+    self,
+    query: &GetReleaseAssetListPageQuery<Cx, Str>,
+  ) -> Result<Page<ReleaseAsset>, Self::GetReleaseAssetListPageError<'_>> {
+    self.call(query).await
+  }
+
+  type GetReleaseAssetError<'req>
+    = <S as Service<&'req GetReleaseAssetQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn get_release_asset(
+    self,
+    query: &GetReleaseAssetQuery<Cx, Str>,
+  ) -> Result<ReleaseAsset, Self::GetReleaseAssetError<'_>> {
+    self.call(query).await
+  }
+
+  type UpdateReleaseAssetError<'req>
+    = <S as Service<&'req UpdateReleaseAssetQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn update_release_asset(
+    self,
+    query: &UpdateReleaseAssetQuery<Cx, Str>,
+  ) -> Result<ReleaseAsset, Self::UpdateReleaseAssetError<'_>> {
+    self.call(query).await
+  }
+
+  type DeleteReleaseAssetError<'req>
+    = <S as Service<&'req DeleteReleaseAssetQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn delete_release_asset(
+    self,
+    query: &DeleteReleaseAssetQuery<Cx, Str>,
+  ) -> Result<(), Self::DeleteReleaseAssetError<'_>> {
+    self.call(query).await
+  }
+
+  type GenerateReleaseNotesError<'req>
+    = <S as Service<&'req GenerateReleaseNotesQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn generate_release_notes(
+    self,
+    query: &GenerateReleaseNotesQuery<Cx, Str>,
+  ) -> Result<ReleaseNotes, Self::GenerateReleaseNotesError<'_>> {
+    self.call(query).await
+  }
+
+  type CreateInstallationAccessTokenError<'req>
+    = <S as Service<&'req CreateInstallationAccessTokenQuery<Cx, Str>>>::Error
+  where
+    Cx: 'req,
+    Str: 'req;
+
+  async fn create_installation_access_token(
+    self,
+    query: &CreateInstallationAccessTokenQuery<Cx, Str>,
+  ) -> Result<InstallationAccessToken, Self::CreateInstallationAccessTokenError<'_>> {
+    self.call(query).await
+  }
 }
 
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -120,11 +505,18 @@ impl PackageStatus {
   }
 }
 
+/// Credentials used to authenticate requests
+///
+/// See <https://docs.github.com/en/rest/authentication/authenticating-to-the-rest-api?apiVersion=2022-11-28>
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum GithubAuth<Token = CompactString> {
-  PrivateToken(Token),
-  JobToken(Token),
+  /// Personal access token, installation access token or OAuth user access token, sent with the `Bearer` scheme.
+  Bearer(Token),
+  /// Same as `Bearer`, but using the legacy `token` scheme.
+  Token(Token),
+  /// OAuth app or GitHub App client credentials, sent with the `Basic` scheme.
+  Basic { client_id: Token, client_secret: Token },
 }
 
 pub type GithubAuthView<'s> = GithubAuth<&'s str>;
@@ -132,15 +524,35 @@ pub type GithubAuthView<'s> = GithubAuth<&'s str>;
 impl<Token: AsRef<str>> GithubAuth<Token> {
   pub fn as_view(&self) -> GithubAuthView<'_> {
     match self {
-      Self::PrivateToken(token) => GithubAuth::PrivateToken(token.as_ref()),
-      Self::JobToken(token) => GithubAuth::JobToken(token.as_ref()),
+      Self::Bearer(token) => GithubAuth::Bearer(token.as_ref()),
+      Self::Token(token) => GithubAuth::Token(token.as_ref()),
+      Self::Basic {
+        client_id,
+        client_secret,
+      } => GithubAuth::Basic {
+        client_id: client_id.as_ref(),
+        client_secret: client_secret.as_ref(),
+      },
     }
   }
 
-  pub fn http_header(&self) -> (&'static str, &str) {
-    match self {
-      Self::PrivateToken(token) => ("PRIVATE-TOKEN", token.as_ref()),
-      Self::JobToken(token) => ("JOB-TOKEN", token.as_ref()),
-    }
+  /// Name and value of the HTTP header carrying these credentials.
+  pub fn http_header(&self) -> (&'static str, String) {
+    let value = match self {
+      Self::Bearer(token) => format!("Bearer {}", token.as_ref()),
+      Self::Token(token) => format!("token {}", token.as_ref()),
+      Self::Basic {
+        client_id,
+        client_secret,
+      } => {
+        use base64::Engine;
+        let credentials = format!("{}:{}", client_id.as_ref(), client_secret.as_ref());
+        format!(
+          "Basic {}",
+          base64::engine::general_purpose::STANDARD.encode(credentials)
+        )
+      }
+    };
+    ("Authorization", value)
   }
 }
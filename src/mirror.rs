@@ -0,0 +1,334 @@
+//! Mirroring of releases to a local directory
+
+use crate::common::digest::{Digest, DigestError};
+use crate::common::project::RepositoryRef;
+use crate::common::release::{Release, ReleaseAsset, ReleaseAssetDownload};
+use crate::common::Page;
+use crate::paginate::Paginate;
+use crate::query::download_release_asset::DownloadReleaseAssetQuery;
+use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
+use compact_str::CompactString;
+use futures::TryStreamExt;
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use tower_service::Service;
+
+/// Name of the JSON index of the releases, in the directory of the repository
+pub const INDEX_FILE: &str = "releases.json";
+
+#[derive(Debug, thiserror::Error)]
+pub enum MirrorError<E> {
+  #[error("failed to list releases")]
+  ListReleases(#[source] E),
+  #[error("failed to download asset {name:?}")]
+  Download {
+    name: String,
+    #[source]
+    source: E,
+  },
+  #[error("failed to read content of asset {name:?}")]
+  ReadAsset {
+    name: String,
+    #[source]
+    source: io::Error,
+  },
+  #[error("unsafe path component: {0:?}")]
+  UnsafePath(String),
+  #[error("size mismatch for asset {name:?}: expected {expected} bytes, got {actual}")]
+  SizeMismatch { name: String, expected: u64, actual: u64 },
+  #[error(transparent)]
+  Digest(#[from] DigestError),
+  #[error("failed to write to the mirror directory")]
+  Io(#[from] io::Error),
+  #[error("failed to write the release index")]
+  Index(#[source] serde_json::Error),
+}
+
+/// Outcome of [`mirror_releases`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MirrorReport {
+  /// Directory of the repository, `None` if it has no release
+  pub directory: Option<PathBuf>,
+  pub releases: usize,
+  /// Paths of the downloaded assets
+  pub downloaded: Vec<PathBuf>,
+  /// Paths of the assets already present
+  pub skipped: Vec<PathBuf>,
+}
+
+/// Mirror all the releases listed with `query` to `root`.
+///
+/// Assets are written to `<root>/<owner>/<repo>/<tag>/<asset>`, and the metadata of the releases to a JSON index
+/// ([`INDEX_FILE`]) in the directory of the repository. Assets already present with the expected size (and digest,
+/// when GitHub reports one) are skipped. Downloads are written to a `.<asset>.part` file first: if the mirroring is
+/// interrupted, they are resumed from where they stopped on the next run.
+///
+/// When `query` refers to the repository by id, its owner and name are read from the URLs of the releases.
+pub async fn mirror_releases<S, Cx, Str, E>(
+  client: &mut S,
+  query: GetProjectReleaseListQuery<Cx, Str>,
+  root: &Path,
+) -> Result<MirrorReport, MirrorError<E>>
+where
+  Cx: Clone,
+  Str: Clone + AsRef<str> + From<CompactString>,
+  S: for<'q> Service<&'q GetProjectReleaseListQuery<Cx, Str>, Response = Page<Release>, Error = E>,
+  S: for<'q> Service<&'q GetProjectReleaseListPageQuery<Cx, Str>, Response = Page<Release>, Error = E>,
+  S: for<'q> Service<&'q DownloadReleaseAssetQuery<Cx, Str>, Response = ReleaseAssetDownload, Error = E>,
+{
+  let (context, auth, repository) = (query.context.clone(), query.auth.clone(), query.repository.clone());
+  let releases: Vec<Release> = Paginate::new(&mut *client, query)
+    .items()
+    .try_collect()
+    .await
+    .map_err(MirrorError::ListReleases)?;
+  let slug = match &repository {
+    RepositoryRef::Slug(slug) => Some((slug.owner.as_ref().to_string(), slug.name.as_ref().to_string())),
+    RepositoryRef::Id(_) => releases.first().and_then(repository_slug),
+  };
+  let Some((owner, name)) = slug else {
+    return Ok(MirrorReport::default());
+  };
+  let directory = root.join(safe_path(&owner)?).join(safe_path(&name)?);
+
+  let mut report = MirrorReport {
+    directory: Some(directory.clone()),
+    releases: releases.len(),
+    ..MirrorReport::default()
+  };
+  for release in &releases {
+    let release_dir = directory.join(safe_path(&release.tag_name)?);
+    for asset in &release.assets {
+      let path = release_dir.join(safe_path(&asset.name)?);
+      if is_present(&path, asset)? {
+        report.skipped.push(path);
+        continue;
+      }
+      fs::create_dir_all(&release_dir)?;
+      let query = DownloadReleaseAssetQuery {
+        context: context.clone(),
+        auth: auth.clone(),
+        repository: repository.clone(),
+        asset: asset.id,
+        offset: None,
+      };
+      download_asset(client, query, asset, &path).await?;
+      report.downloaded.push(path);
+    }
+  }
+
+  fs::create_dir_all(&directory)?;
+  let index = serde_json::to_vec_pretty(&releases).map_err(MirrorError::Index)?;
+  let index_path = directory.join(INDEX_FILE);
+  let tmp_path = sibling(&index_path, ".tmp");
+  fs::write(&tmp_path, index)?;
+  fs::rename(&tmp_path, &index_path)?;
+  Ok(report)
+}
+
+/// Download `asset` to `path`, resuming from its `.part` file if there is one
+async fn download_asset<S, Cx, Str, E>(
+  client: &mut S,
+  mut query: DownloadReleaseAssetQuery<Cx, Str>,
+  asset: &ReleaseAsset,
+  path: &Path,
+) -> Result<(), MirrorError<E>>
+where
+  S: for<'q> Service<&'q DownloadReleaseAssetQuery<Cx, Str>, Response = ReleaseAssetDownload, Error = E>,
+{
+  let part_path = sibling(path, ".part");
+  let received = match fs::metadata(&part_path) {
+    Ok(metadata) if metadata.len() < asset.size => metadata.len(),
+    Ok(_) => 0,
+    Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+    Err(e) => return Err(e.into()),
+  };
+  query.offset = (received > 0).then_some(received);
+  let download = client.call(&query).await.map_err(|source| MirrorError::Download {
+    name: asset.name.clone(),
+    source,
+  })?;
+  // Servers may ignore the range and send the whole content
+  let mut file = if received > 0 && download.offset == received {
+    fs::OpenOptions::new().append(true).open(&part_path)?
+  } else {
+    fs::File::create(&part_path)?
+  };
+  let mut content = download.content;
+  while let Some(chunk) = content.try_next().await.map_err(|source| MirrorError::ReadAsset {
+    name: asset.name.clone(),
+    source,
+  })? {
+    file.write_all(&chunk)?;
+  }
+  file.sync_all()?;
+  drop(file);
+
+  let size = fs::metadata(&part_path)?.len();
+  if size != asset.size {
+    fs::remove_file(&part_path)?;
+    return Err(MirrorError::SizeMismatch {
+      name: asset.name.clone(),
+      expected: asset.size,
+      actual: size,
+    });
+  }
+  if let Some(expected) = &asset.digest {
+    if let Some(actual) = file_digest(&part_path, expected)? {
+      if actual != *expected {
+        fs::remove_file(&part_path)?;
+        return Err(
+          DigestError::Mismatch {
+            expected: Box::new(expected.clone()),
+            actual: Box::new(actual),
+          }
+          .into(),
+        );
+      }
+    }
+  }
+  fs::rename(&part_path, path)?;
+  Ok(())
+}
+
+/// Whether `path` holds the content of `asset`, judging by its size and digest
+fn is_present(path: &Path, asset: &ReleaseAsset) -> io::Result<bool> {
+  match fs::metadata(path) {
+    Ok(metadata) if metadata.len() != asset.size => return Ok(false),
+    Ok(_) => {}
+    Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+    Err(e) => return Err(e),
+  }
+  Ok(match &asset.digest {
+    Some(expected) => file_digest(path, expected)?.map_or(true, |actual| actual == *expected),
+    None => true,
+  })
+}
+
+/// Digest of the file at `path`, with the algorithm of `expected`; `None` if the algorithm is not supported
+fn file_digest(path: &Path, expected: &Digest) -> io::Result<Option<Digest>> {
+  let Ok(mut hasher) = expected.hasher() else {
+    return Ok(None);
+  };
+  let mut file = fs::File::open(path)?;
+  let mut buffer = vec![0; 64 * 1024];
+  loop {
+    let read = file.read(&mut buffer)?;
+    if read == 0 {
+      return Ok(Some(hasher.finalize()));
+    }
+    hasher.update(&buffer[..read]);
+  }
+}
+
+/// Owner and name of the repository of `release`, read from its API URL (`.../repos/<owner>/<repo>/releases/<id>`)
+fn repository_slug(release: &Release) -> Option<(String, String)> {
+  let mut segments = release
+    .url
+    .path_segments()?
+    .skip_while(|segment| *segment != "repos")
+    .skip(1);
+  let owner = segments.next()?;
+  let name = segments.next()?;
+  Some((owner.to_string(), name.to_string()))
+}
+
+/// Relative path for a tag or asset name; tags with slashes (e.g. `release/1.0`) map to nested directories
+fn safe_path<E>(name: &str) -> Result<PathBuf, MirrorError<E>> {
+  let is_safe = |component: &str| {
+    !matches!(component, "" | "." | "..") && !component.contains(['\\', ':', '\0']) && !component.starts_with('.')
+  };
+  if name.split('/').all(is_safe) {
+    Ok(name.split('/').collect())
+  } else {
+    Err(MirrorError::UnsafePath(name.to_string()))
+  }
+}
+
+/// Hidden file next to `path`, used while it is being written
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+  let mut name = OsString::from(".");
+  name.push(path.file_name().unwrap_or_default());
+  name.push(suffix);
+  path.with_file_name(name)
+}
+
+#[cfg(all(test, feature = "fake"))]
+mod test {
+  use super::*;
+  use crate::common::project::ProjectId;
+  use crate::fake::FakeGithub;
+
+  /// Fake repository `octocat/hello` with two releases
+  fn hello_releases() -> (FakeGithub, ProjectId) {
+    let github = FakeGithub::new();
+    let id = github.add_repository("octocat", "hello");
+    github.publish_release(
+      id,
+      "v1.0.0",
+      &[("hello-1.0.0.tar.gz", b"1.0.0 sources"), ("SHA256SUMS", b"checksums")],
+    );
+    github.publish_release(id, "v1.1.0", &[("hello-1.1.0.tar.gz", b"1.1.0 sources")]);
+    (github, id)
+  }
+
+  #[tokio::test]
+  async fn mirror_and_skip_present_assets() {
+    let (mut github, id) = hello_releases();
+    let root = tempfile::tempdir().unwrap();
+    let query = GetProjectReleaseListQuery::<_, CompactString>::new(RepositoryRef::Id(id));
+
+    let report = mirror_releases(&mut github, query.clone(), root.path()).await.unwrap();
+    let directory = root.path().join("octocat/hello");
+    assert_eq!(report.directory.as_deref(), Some(directory.as_path()));
+    assert_eq!(
+      (report.releases, report.downloaded.len(), report.skipped.len()),
+      (2, 3, 0)
+    );
+    let sources = directory.join("v1.1.0/hello-1.1.0.tar.gz");
+    assert_eq!(fs::read(&sources).unwrap(), b"1.1.0 sources");
+    let index: Vec<Release> = serde_json::from_slice(&fs::read(directory.join(INDEX_FILE)).unwrap()).unwrap();
+    assert_eq!(index, github.releases(id));
+
+    fs::write(&sources, b"1.1.0 sourcez").unwrap();
+    let report = mirror_releases(&mut github, query, root.path()).await.unwrap();
+    assert_eq!(report.downloaded, [sources.as_path()]);
+    assert_eq!(report.skipped.len(), 2);
+    assert_eq!(fs::read(&sources).unwrap(), b"1.1.0 sources");
+  }
+
+  #[tokio::test]
+  async fn resume_interrupted_download() {
+    let (mut github, _) = hello_releases();
+    let root = tempfile::tempdir().unwrap();
+    let release_dir = root.path().join("octocat/hello/v1.0.0");
+    fs::create_dir_all(&release_dir).unwrap();
+    fs::write(release_dir.join(".hello-1.0.0.tar.gz.part"), b"1.0.0").unwrap();
+    let query = GetProjectReleaseListQuery::new(RepositoryRef::Slug(crate::common::project::ProjectSlug::new(
+      CompactString::new("octocat"),
+      CompactString::new("hello"),
+    )));
+
+    let report = mirror_releases(&mut github, query, root.path()).await.unwrap();
+    assert_eq!(report.downloaded.len(), 3);
+    assert_eq!(
+      fs::read(release_dir.join("hello-1.0.0.tar.gz")).unwrap(),
+      b"1.0.0 sources"
+    );
+    assert!(!release_dir.join(".hello-1.0.0.tar.gz.part").exists());
+  }
+
+  #[test]
+  fn reject_unsafe_paths() {
+    assert_eq!(
+      safe_path::<()>("release/1.0").unwrap(),
+      Path::new("release").join("1.0")
+    );
+    assert!(safe_path::<()>("../etc").is_err());
+    assert!(safe_path::<()>("/etc").is_err());
+    assert!(safe_path::<()>(".part").is_err());
+  }
+}
@@ -0,0 +1,393 @@
+use crate::common::release::{Release, ReleaseAsset};
+use crate::common::Page;
+use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+use crate::query::get_project_release_list_page::GetProjectReleaseListPageQuery;
+use crate::query::get_release_asset_list::GetReleaseAssetListQuery;
+use crate::query::get_release_asset_list_page::GetReleaseAssetListPageQuery;
+use crate::GithubClient;
+use compact_str::CompactString;
+use core::marker::PhantomData;
+use futures::{stream, Stream, TryStreamExt};
+use std::future::Future;
+use tower_service::Service;
+
+/// Query for the first page of a paginated list
+pub trait PaginatedQuery {
+  type Item;
+  /// Query type used to fetch the following pages
+  type PageQuery;
+
+  /// Create the query for the page at `cursor`, reusing the context and auth of this query.
+  fn page_query(&self, cursor: CompactString) -> Self::PageQuery;
+}
+
+impl<Cx, Str> PaginatedQuery for GetProjectReleaseListQuery<Cx, Str>
+where
+  Cx: Clone,
+  Str: Clone + From<CompactString>,
+{
+  type Item = Release;
+  type PageQuery = GetProjectReleaseListPageQuery<Cx, Str>;
+
+  fn page_query(&self, cursor: CompactString) -> Self::PageQuery {
+    GetProjectReleaseListPageQuery {
+      context: self.context.clone(),
+      auth: self.auth.clone(),
+      cursor: Str::from(cursor),
+    }
+  }
+}
+
+impl<Cx, Str> PaginatedQuery for GetReleaseAssetListQuery<Cx, Str>
+where
+  Cx: Clone,
+  Str: Clone + From<CompactString>,
+{
+  type Item = ReleaseAsset;
+  type PageQuery = GetReleaseAssetListPageQuery<Cx, Str>;
+
+  fn page_query(&self, cursor: CompactString) -> Self::PageQuery {
+    GetReleaseAssetListPageQuery {
+      context: self.context.clone(),
+      auth: self.auth.clone(),
+      cursor: Str::from(cursor),
+    }
+  }
+}
+
+/// Client fetching the pages of a [`PaginatedQuery`]
+///
+/// It is implemented for `&mut S` when `S` implements the services for both the first page query and its page query,
+/// such as [`HttpGithubClient`](crate::client::http::HttpGithubClient), and for [`GithubClient`] implementations
+/// wrapped in [`GithubClientPages`].
+pub trait PageSource<Q: PaginatedQuery> {
+  type Error;
+
+  fn first_page(&mut self, query: &Q) -> impl Future<Output = Result<Page<Q::Item>, Self::Error>>;
+
+  fn next_page(&mut self, query: &Q::PageQuery) -> impl Future<Output = Result<Page<Q::Item>, Self::Error>>;
+}
+
+impl<S, Q, E> PageSource<Q> for &'_ mut S
+where
+  Q: PaginatedQuery,
+  S: for<'q> Service<&'q Q, Response = Page<Q::Item>, Error = E>,
+  S: for<'q> Service<&'q Q::PageQuery, Response = Page<Q::Item>, Error = E>,
+{
+  type Error = E;
+
+  fn first_page(&mut self, query: &Q) -> impl Future<Output = Result<Page<Q::Item>, E>> {
+    (**self).call(query)
+  }
+
+  fn next_page(&mut self, query: &Q::PageQuery) -> impl Future<Output = Result<Page<Q::Item>, E>> {
+    (**self).call(query)
+  }
+}
+
+/// [`PageSource`] sending the list queries through the methods of a [`GithubClient`]
+///
+/// The errors of the first page and page queries are converted into `E`. As the error types of [`GithubClient`] may
+/// borrow the query, the context and string types of the queries must be `'static` (e.g. [`Context`] and
+/// [`CompactString`]).
+///
+/// [`Context`]: crate::context::Context
+pub struct GithubClientPages<'c, C, E> {
+  client: &'c mut C,
+  error: PhantomData<fn() -> E>,
+}
+
+impl<'c, C, E> GithubClientPages<'c, C, E> {
+  pub fn new(client: &'c mut C) -> Self {
+    Self {
+      client,
+      error: PhantomData,
+    }
+  }
+}
+
+impl<C, Cx, Str, E> PageSource<GetProjectReleaseListQuery<Cx, Str>> for GithubClientPages<'_, C, E>
+where
+  Cx: Clone,
+  Str: Clone + From<CompactString>,
+  for<'a> &'a mut C: GithubClient<Cx, Str>,
+  for<'a, 'r> <&'a mut C as GithubClient<Cx, Str>>::GetProjectReleaseListError<'r>: Into<E>,
+  for<'a, 'r> <&'a mut C as GithubClient<Cx, Str>>::GetProjectReleaseListPageError<'r>: Into<E>,
+{
+  type Error = E;
+
+  async fn first_page(&mut self, query: &GetProjectReleaseListQuery<Cx, Str>) -> Result<Page<Release>, E> {
+    self.client.get_project_release_list(query).await.map_err(Into::into)
+  }
+
+  async fn next_page(&mut self, query: &GetProjectReleaseListPageQuery<Cx, Str>) -> Result<Page<Release>, E> {
+    self
+      .client
+      .get_project_release_list_page(query)
+      .await
+      .map_err(Into::into)
+  }
+}
+
+impl<C, Cx, Str, E> PageSource<GetReleaseAssetListQuery<Cx, Str>> for GithubClientPages<'_, C, E>
+where
+  Cx: Clone,
+  Str: Clone + From<CompactString>,
+  for<'a> &'a mut C: GithubClient<Cx, Str>,
+  for<'a, 'r> <&'a mut C as GithubClient<Cx, Str>>::GetReleaseAssetListError<'r>: Into<E>,
+  for<'a, 'r> <&'a mut C as GithubClient<Cx, Str>>::GetReleaseAssetListPageError<'r>: Into<E>,
+{
+  type Error = E;
+
+  async fn first_page(&mut self, query: &GetReleaseAssetListQuery<Cx, Str>) -> Result<Page<ReleaseAsset>, E> {
+    self.client.get_release_asset_list(query).await.map_err(Into::into)
+  }
+
+  async fn next_page(&mut self, query: &GetReleaseAssetListPageQuery<Cx, Str>) -> Result<Page<ReleaseAsset>, E> {
+    self.client.get_release_asset_list_page(query).await.map_err(Into::into)
+  }
+}
+
+/// Adapter following the `next` cursors of a paginated list
+///
+/// It works with any [`PageSource`]: clients implementing the services for both the first page query and its page
+/// query with [`Paginate::new`], and [`GithubClient`] implementations with [`Paginate::with_github_client`]. Pages
+/// are only fetched when the stream is polled, so dropping the stream stops the pagination.
+pub struct Paginate<S, Q> {
+  client: S,
+  query: Q,
+  limit: Option<usize>,
+}
+
+enum Step {
+  First,
+  Next(CompactString),
+  Done,
+}
+
+struct State<S, Q> {
+  client: S,
+  query: Q,
+  remaining: Option<usize>,
+  step: Step,
+}
+
+impl<S, Q> Paginate<S, Q> {
+  pub fn new(client: S, query: Q) -> Self {
+    Self {
+      client,
+      query,
+      limit: None,
+    }
+  }
+
+  /// Stop after `limit` items; the last page is truncated if needed.
+  pub fn limit(mut self, limit: usize) -> Self {
+    self.limit = Some(limit);
+    self
+  }
+}
+
+impl<'c, C, E, Q> Paginate<GithubClientPages<'c, C, E>, Q> {
+  /// Paginate with a [`GithubClient`], converting its errors into `E`
+  pub fn with_github_client(client: &'c mut C, query: Q) -> Self {
+    Self::new(GithubClientPages::new(client), query)
+  }
+}
+
+impl<S, Q> Paginate<S, Q>
+where
+  Q: PaginatedQuery,
+  S: PageSource<Q>,
+{
+  /// Stream of pages
+  ///
+  /// The stream ends after the last page, once the item limit is reached, or after the first error.
+  pub fn pages(self) -> impl Stream<Item = Result<Page<Q::Item>, S::Error>> {
+    let state = State {
+      client: self.client,
+      query: self.query,
+      remaining: self.limit,
+      step: if self.limit == Some(0) { Step::Done } else { Step::First },
+    };
+    stream::unfold(state, |mut state| async move {
+      let page = match core::mem::replace(&mut state.step, Step::Done) {
+        Step::Done => return None,
+        Step::First => state.client.first_page(&state.query).await,
+        Step::Next(cursor) => {
+          let query = state.query.page_query(cursor);
+          state.client.next_page(&query).await
+        }
+      };
+      let mut page = match page {
+        Ok(page) => page,
+        Err(e) => return Some((Err(e), state)),
+      };
+      if let Some(remaining) = state.remaining.as_mut() {
+        page.items.truncate(*remaining);
+        *remaining -= page.items.len();
+        if *remaining == 0 {
+          return Some((Ok(page), state));
+        }
+      }
+      if let Some(next) = page.next.clone() {
+        state.step = Step::Next(next);
+      }
+      Some((Ok(page), state))
+    })
+  }
+
+  /// Stream of items, across all the pages
+  pub fn items(self) -> impl Stream<Item = Result<Q::Item, S::Error>> {
+    self
+      .pages()
+      .map_ok(|page| stream::iter(page.items.into_iter().map(Ok)))
+      .try_flatten()
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::common::project::{ProjectId, RepositoryRef};
+  use crate::context::EmptyContext;
+  use core::task::{Context, Poll};
+  use futures::StreamExt;
+  use std::convert::Infallible;
+  use std::future::{ready, Ready};
+
+  /// Client serving pages of numbers, with the page index as cursor
+  struct Numbers {
+    pages: Vec<Vec<u32>>,
+    calls: usize,
+  }
+
+  impl Numbers {
+    fn page(&mut self, index: usize) -> Page<u32> {
+      self.calls += 1;
+      Page {
+        first: Some(CompactString::new("0")),
+        next: (index + 1 < self.pages.len()).then(|| CompactString::from((index + 1).to_string())),
+        last: Some(CompactString::from((self.pages.len() - 1).to_string())),
+        rate_limit: None,
+        items: self.pages[index].clone(),
+      }
+    }
+  }
+
+  struct NumberListQuery;
+
+  impl PaginatedQuery for NumberListQuery {
+    type Item = u32;
+    type PageQuery = GetProjectReleaseListPageQuery<EmptyContext, CompactString>;
+
+    fn page_query(&self, cursor: CompactString) -> Self::PageQuery {
+      GetProjectReleaseListPageQuery::new(cursor)
+    }
+  }
+
+  impl Service<&'_ NumberListQuery> for Numbers {
+    type Response = Page<u32>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _req: &NumberListQuery) -> Self::Future {
+      ready(Ok(self.page(0)))
+    }
+  }
+
+  impl Service<&'_ GetProjectReleaseListPageQuery<EmptyContext, CompactString>> for Numbers {
+    type Response = Page<u32>;
+    type Error = Infallible;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+      Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: &GetProjectReleaseListPageQuery<EmptyContext, CompactString>) -> Self::Future {
+      ready(Ok(self.page(req.cursor.parse().unwrap())))
+    }
+  }
+
+  #[tokio::test]
+  async fn follow_next_cursors() {
+    let mut client = Numbers {
+      pages: vec![vec![1, 2], vec![3, 4], vec![5]],
+      calls: 0,
+    };
+    let items: Vec<u32> = Paginate::new(&mut client, NumberListQuery)
+      .items()
+      .map(Result::unwrap)
+      .collect()
+      .await;
+    assert_eq!(items, vec![1, 2, 3, 4, 5]);
+    assert_eq!(client.calls, 3);
+  }
+
+  #[tokio::test]
+  async fn stop_at_limit() {
+    let mut client = Numbers {
+      pages: vec![vec![1, 2], vec![3, 4], vec![5]],
+      calls: 0,
+    };
+    let items: Vec<u32> = Paginate::new(&mut client, NumberListQuery)
+      .limit(3)
+      .items()
+      .map(Result::unwrap)
+      .collect()
+      .await;
+    assert_eq!(items, vec![1, 2, 3]);
+    assert_eq!(client.calls, 2);
+  }
+
+  #[cfg(feature = "fake")]
+  #[tokio::test]
+  async fn paginate_with_github_client() {
+    use crate::client::http::HttpGithubClientError;
+    use crate::common::Pagination;
+    use crate::fake::FakeGithub;
+    use std::num::NonZeroU8;
+
+    let mut github = FakeGithub::new();
+    let id = github
+      .load_releases(
+        "octocat",
+        "Hello-World",
+        "./test-resources/fake/octocat-hello-world/releases.json",
+      )
+      .unwrap();
+    let mut query = GetProjectReleaseListQuery::<_, CompactString>::new(RepositoryRef::Id(id));
+    query.pagination = Some(Pagination {
+      per_page: NonZeroU8::new(3),
+    });
+    let pages: Vec<Page<Release>> = Paginate::with_github_client(&mut github, query)
+      .pages()
+      .try_collect::<Vec<_>>()
+      .await
+      .map_err(|e: HttpGithubClientError| e)
+      .unwrap();
+    assert_eq!(
+      pages.iter().map(|page| page.items.len()).collect::<Vec<_>>(),
+      vec![3, 1]
+    );
+  }
+
+  #[test]
+  fn build_release_page_query() {
+    let query = GetProjectReleaseListQuery::<_, CompactString>::new(RepositoryRef::Id(ProjectId::new(1)));
+    let page_query = query.page_query(CompactString::new(
+      "https://api.github.com/repositories/1/releases?page=2",
+    ));
+    assert_eq!(
+      page_query,
+      GetProjectReleaseListPageQuery::new(CompactString::new(
+        "https://api.github.com/repositories/1/releases?page=2"
+      ))
+    );
+  }
+}
@@ -0,0 +1,391 @@
+//! Selection of the release asset built for a platform
+
+use crate::common::release::{Release, ReleaseAsset};
+use compact_str::CompactString;
+
+/// Platform an asset is built for, using the names of [`std::env::consts`] (e.g. `x86_64`, `linux`)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Target {
+  pub arch: CompactString,
+  pub os: CompactString,
+  /// C library or ABI, such as `gnu`, `musl` or `msvc`
+  pub env: Option<CompactString>,
+}
+
+impl Target {
+  /// Platform of the running program
+  pub fn current() -> Self {
+    let env = if cfg!(target_env = "gnu") {
+      Some("gnu")
+    } else if cfg!(target_env = "musl") {
+      Some("musl")
+    } else if cfg!(target_env = "msvc") {
+      Some("msvc")
+    } else {
+      None
+    };
+    Self {
+      arch: CompactString::new(std::env::consts::ARCH),
+      os: CompactString::new(std::env::consts::OS),
+      env: env.map(CompactString::new),
+    }
+  }
+
+  /// Read a Rust target triple, such as `x86_64-unknown-linux-gnu` or `aarch64-apple-darwin`
+  pub fn from_triple(triple: &str) -> Option<Self> {
+    let mut parts = triple.split('-');
+    let arch = match parts.next()? {
+      "i386" | "i586" | "i686" => "x86",
+      arch if arch.starts_with("armv7") => "arm",
+      arch if arch.starts_with("riscv64") => "riscv64",
+      arch => arch,
+    };
+    let rest: Vec<&str> = parts.collect();
+    let os = ["linux", "darwin", "windows", "freebsd", "netbsd", "android"]
+      .into_iter()
+      .find(|os| rest.contains(os))?;
+    let env = rest
+      .last()
+      .and_then(|last| ["gnu", "musl", "msvc"].into_iter().find(|env| last.starts_with(env)));
+    Some(Self {
+      arch: CompactString::new(arch),
+      os: CompactString::new(if os == "darwin" { "macos" } else { os }),
+      env: env.map(CompactString::new),
+    })
+  }
+
+  fn arch_aliases(&self) -> &[&str] {
+    match self.arch.as_str() {
+      "x86_64" => &["x86_64", "x86-64", "amd64", "x64"],
+      "aarch64" => &["aarch64", "arm64"],
+      "x86" => &["i686", "i586", "i386", "386", "x86", "win32"],
+      "arm" => &["armv7", "armhf", "arm"],
+      "riscv64" => &["riscv64", "riscv64gc"],
+      "powerpc64" => &["ppc64"],
+      "s390x" => &["s390x"],
+      _ => &[],
+    }
+  }
+
+  fn os_aliases(&self) -> &[&str] {
+    match self.os.as_str() {
+      "linux" => &["linux"],
+      "macos" => &["darwin", "macos", "osx", "apple", "mac"],
+      "windows" => &["windows", "win64", "win32", "win"],
+      "freebsd" => &["freebsd"],
+      "netbsd" => &["netbsd"],
+      "android" => &["android"],
+      _ => &[],
+    }
+  }
+}
+
+/// Extensions of files that are not programs or archives, such as checksums, signatures and OS packages
+const IGNORED_EXTENSIONS: [&str; 15] = [
+  ".sha256",
+  ".sha512",
+  ".sha256sum",
+  ".sig",
+  ".asc",
+  ".pem",
+  ".sbom",
+  ".json",
+  ".txt",
+  ".deb",
+  ".rpm",
+  ".msi",
+  ".dmg",
+  ".pkg",
+  ".apk",
+];
+
+/// Picks the asset of a release built for a target platform.
+///
+/// Asset names are matched against common spellings of the architecture and OS (e.g. `x86_64-unknown-linux-gnu`,
+/// `linux-amd64` or `darwin-arm64`), then ranked with [`AssetMatcher::score`]. Names set with
+/// [`AssetMatcher::with_override`] take precedence, to settle ambiguous cases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetMatcher {
+  target: Target,
+  overrides: Vec<CompactString>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AssetMatchError {
+  #[error("no asset matches the target platform")]
+  NotFound,
+  #[error("several assets match the target platform equally: {0:?}")]
+  Ambiguous(Vec<String>),
+}
+
+impl AssetMatcher {
+  pub fn new(target: Target) -> Self {
+    Self {
+      target,
+      overrides: Vec::new(),
+    }
+  }
+
+  /// Matcher for the platform of the running program
+  pub fn current() -> Self {
+    Self::new(Target::current())
+  }
+
+  pub fn target(&self) -> &Target {
+    &self.target
+  }
+
+  /// Select the asset named `name` when the release has it, without scoring.
+  ///
+  /// Overrides are tried in the order they were added.
+  pub fn with_override(mut self, name: impl Into<CompactString>) -> Self {
+    self.overrides.push(name.into());
+    self
+  }
+
+  /// Score of an asset name for the target, higher is better; `None` if the asset is not built for the target
+  ///
+  /// The platform match (architecture, then C library) ranks first. Among assets built for the same platform, common
+  /// archive formats (`.tar.gz`, `.tgz` and `.zip`) are preferred over less common ones (`.tar.xz`, `.tar.zst`, ...),
+  /// and both over bare executables.
+  pub fn score(&self, name: &str) -> Option<u32> {
+    let name = name.to_ascii_lowercase();
+    if IGNORED_EXTENSIONS.iter().any(|ext| name.ends_with(ext)) {
+      return None;
+    }
+    if !self.target.os_aliases().iter().any(|os| contains_word(&name, os)) {
+      return None;
+    }
+    let mut platform = if self.target.arch_aliases().iter().any(|arch| contains_word(&name, arch)) {
+      8
+    } else if self.target.os == "macos" && contains_word(&name, "universal") {
+      // Fat binaries run on all the architectures
+      4
+    } else {
+      return None;
+    };
+
+    let asset_env = ["gnu", "musl", "msvc"]
+      .into_iter()
+      .find(|env| contains_word(&name, env));
+    platform += match (self.target.env.as_deref(), asset_env) {
+      (Some(target), Some(asset)) if target == asset => 4,
+      (_, None) => 2,
+      // Statically linked binaries also run on glibc systems
+      (Some("gnu"), Some("musl")) => 1,
+      _ => return None,
+    };
+
+    let is_windows = self.target.os == "windows";
+    let format = if [".tar.gz", ".tgz"].iter().any(|ext| name.ends_with(ext)) {
+      if is_windows {
+        2
+      } else {
+        3
+      }
+    } else if name.ends_with(".zip") || (is_windows && name.ends_with(".exe")) {
+      if is_windows {
+        3
+      } else {
+        2
+      }
+    } else if [".tar.xz", ".txz", ".tar.zst", ".tar.bz2"]
+      .iter()
+      .any(|ext| name.ends_with(ext))
+    {
+      1
+    } else if name.ends_with(".exe") {
+      return None;
+    } else {
+      // Bare executable
+      0
+    };
+    Some(platform * 4 + format)
+  }
+
+  /// Select the asset of `release` built for the target.
+  pub fn select<'r>(&self, release: &'r Release) -> Result<&'r ReleaseAsset, AssetMatchError> {
+    self.select_from(&release.assets)
+  }
+
+  /// Select the asset built for the target among `assets`.
+  pub fn select_from<'a>(&self, assets: &'a [ReleaseAsset]) -> Result<&'a ReleaseAsset, AssetMatchError> {
+    if let Some(asset) = self
+      .overrides
+      .iter()
+      .find_map(|name| assets.iter().find(|asset| asset.name == name.as_str()))
+    {
+      return Ok(asset);
+    }
+    let scored: Vec<(u32, &ReleaseAsset)> = assets
+      .iter()
+      .filter_map(|asset| Some((self.score(&asset.name)?, asset)))
+      .collect();
+    let best = scored
+      .iter()
+      .map(|(score, _)| *score)
+      .max()
+      .ok_or(AssetMatchError::NotFound)?;
+    let mut candidates = scored.into_iter().filter(|(score, _)| *score == best);
+    let (_, asset) = candidates.next().expect("the best score comes from an asset");
+    let others: Vec<String> = candidates.map(|(_, asset)| asset.name.clone()).collect();
+    if others.is_empty() {
+      Ok(asset)
+    } else {
+      Err(AssetMatchError::Ambiguous(
+        core::iter::once(asset.name.clone()).chain(others).collect(),
+      ))
+    }
+  }
+}
+
+/// Whether `word` appears in `name` without being part of a longer alphanumeric word (e.g. `arm` in `arm64`)
+fn contains_word(name: &str, word: &str) -> bool {
+  name.match_indices(word).any(|(start, _)| {
+    let before = name[..start].chars().next_back();
+    let after = &name[start + word.len()..];
+    let is_boundary = |c: Option<char>| !c.is_some_and(|c| c.is_ascii_alphanumeric());
+    // `x86` is also the start of `x86_64`
+    let is_x86_64 = word == "x86" && (after.starts_with("_64") || after.starts_with("-64"));
+    is_boundary(before) && is_boundary(after.chars().next()) && !is_x86_64
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::common::release::ReleaseAssetId;
+  use chrono::Utc;
+  use url::Url;
+
+  fn matcher(triple: &str) -> AssetMatcher {
+    AssetMatcher::new(Target::from_triple(triple).unwrap())
+  }
+
+  fn assets(names: &[&str]) -> Vec<ReleaseAsset> {
+    let url = Url::parse("https://api.github.com/repos/octocat/Hello-World/releases/assets/1").unwrap();
+    names
+      .iter()
+      .zip(1..)
+      .map(|(name, id)| ReleaseAsset {
+        url: url.clone(),
+        browser_download_url: url.clone(),
+        id: ReleaseAssetId::new(id),
+        node_id: format!("RA_{id}"),
+        name: name.to_string(),
+        label: None,
+        state: String::from("uploaded"),
+        content_type: String::from("application/octet-stream"),
+        size: 0,
+        digest: None,
+        download_count: 0,
+        created_at: Utc::now(),
+        updated_at: Utc::now(),
+        uploader: None,
+      })
+      .collect()
+  }
+
+  fn select(matcher: &AssetMatcher, names: &[&str]) -> Result<String, AssetMatchError> {
+    matcher.select_from(&assets(names)).map(|asset| asset.name.clone())
+  }
+
+  const ASSETS: [&str; 10] = [
+    "hello-v1.2.0-x86_64-unknown-linux-gnu.tar.gz",
+    "hello-v1.2.0-x86_64-unknown-linux-musl.tar.gz",
+    "hello-v1.2.0-aarch64-apple-darwin.tar.gz",
+    "hello-v1.2.0-x86_64-pc-windows-msvc.zip",
+    "hello_1.2.0_linux_arm64.tar.gz",
+    "hello_1.2.0_darwin_amd64.tar.gz",
+    "hello-i686-pc-windows-msvc.exe",
+    "hello-v1.2.0-riscv64gc-unknown-linux-gnu.tar.gz",
+    "hello-v1.2.0-x86_64-unknown-linux-gnu.tar.gz.sha256",
+    "SHA256SUMS",
+  ];
+
+  #[test]
+  fn read_target_triples() {
+    assert_eq!(
+      Target::from_triple("aarch64-apple-darwin"),
+      Some(Target {
+        arch: CompactString::new("aarch64"),
+        os: CompactString::new("macos"),
+        env: None,
+      })
+    );
+    assert_eq!(
+      Target::from_triple("i686-pc-windows-msvc").map(|target| target.arch),
+      Some(CompactString::new("x86"))
+    );
+    assert_eq!(
+      Target::from_triple("riscv64gc-unknown-linux-musl").map(|target| target.arch),
+      Some(CompactString::new("riscv64"))
+    );
+    assert_eq!(Target::from_triple("wasm32-unknown-unknown"), None);
+  }
+
+  #[test]
+  fn match_common_naming_schemes() {
+    let cases = [
+      (
+        "x86_64-unknown-linux-gnu",
+        "hello-v1.2.0-x86_64-unknown-linux-gnu.tar.gz",
+      ),
+      (
+        "x86_64-unknown-linux-musl",
+        "hello-v1.2.0-x86_64-unknown-linux-musl.tar.gz",
+      ),
+      ("aarch64-apple-darwin", "hello-v1.2.0-aarch64-apple-darwin.tar.gz"),
+      ("x86_64-apple-darwin", "hello_1.2.0_darwin_amd64.tar.gz"),
+      ("aarch64-unknown-linux-gnu", "hello_1.2.0_linux_arm64.tar.gz"),
+      ("x86_64-pc-windows-msvc", "hello-v1.2.0-x86_64-pc-windows-msvc.zip"),
+      ("i686-pc-windows-msvc", "hello-i686-pc-windows-msvc.exe"),
+      (
+        "riscv64gc-unknown-linux-gnu",
+        "hello-v1.2.0-riscv64gc-unknown-linux-gnu.tar.gz",
+      ),
+    ];
+    for (triple, expected) in cases {
+      assert_eq!(select(&matcher(triple), &ASSETS).as_deref(), Ok(expected), "{triple}");
+    }
+    assert_eq!(
+      select(&matcher("s390x-unknown-linux-gnu"), &ASSETS),
+      Err(AssetMatchError::NotFound)
+    );
+  }
+
+  #[test]
+  fn report_ambiguous_assets_unless_overridden() {
+    let names = ["hello-linux-amd64.tar.gz", "hello-cli-linux-amd64.tar.gz"];
+    let matcher = matcher("x86_64-unknown-linux-gnu");
+    assert_eq!(
+      select(&matcher, &names),
+      Err(AssetMatchError::Ambiguous(names.map(String::from).to_vec()))
+    );
+    let matcher = matcher.with_override("hello-cli-linux-amd64.tar.gz");
+    assert_eq!(select(&matcher, &names).as_deref(), Ok("hello-cli-linux-amd64.tar.gz"));
+  }
+
+  #[test]
+  fn prefer_common_archive_formats() {
+    let cases = [
+      (
+        "x86_64-unknown-linux-gnu",
+        [
+          "hello-x86_64-unknown-linux-gnu.tar.xz",
+          "hello-x86_64-unknown-linux-gnu.tar.gz",
+        ],
+      ),
+      (
+        "x86_64-pc-windows-msvc",
+        [
+          "hello-x86_64-pc-windows-msvc.tar.zst",
+          "hello-x86_64-pc-windows-msvc.zip",
+        ],
+      ),
+    ];
+    for (triple, names) in cases {
+      assert_eq!(select(&matcher(triple), &names).as_deref(), Ok(names[1]), "{triple}");
+    }
+  }
+}
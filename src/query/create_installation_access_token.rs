@@ -0,0 +1,49 @@
+use crate::common::app::InstallationId;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Create an installation access token for an app
+///
+/// The request must be authenticated with the app JWT, see [`crate::app::GithubAppKey`].
+///
+/// <https://docs.github.com/en/rest/apps/apps?apiVersion=2022-11-28#create-an-installation-access-token-for-an-app>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateInstallationAccessTokenQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub installation: InstallationId,
+}
+
+pub type CreateInstallationAccessTokenQueryView<'req, Cx> = CreateInstallationAccessTokenQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> CreateInstallationAccessTokenQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> CreateInstallationAccessTokenQuery<NewCx, Str> {
+    CreateInstallationAccessTokenQuery {
+      context: new_context,
+      auth: self.auth,
+      installation: self.installation,
+    }
+  }
+
+  pub fn as_view(&self) -> CreateInstallationAccessTokenQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    CreateInstallationAccessTokenQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      installation: self.installation,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> CreateInstallationAccessTokenQuery<EmptyContext, Str> {
+  pub const fn new(installation: InstallationId) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      installation,
+    }
+  }
+}
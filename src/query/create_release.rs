@@ -0,0 +1,54 @@
+use crate::common::project::RepositoryRef;
+use crate::common::release::InputRelease;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Create a release
+///
+/// Fails with [`Conflict`](crate::client::http::HttpGithubClientError::Conflict) if a release already exists for the tag.
+///
+/// <https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#create-a-release>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CreateReleaseQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  pub input: InputRelease<Str>,
+}
+
+pub type CreateReleaseQueryView<'req, Cx> = CreateReleaseQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> CreateReleaseQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> CreateReleaseQuery<NewCx, Str> {
+    CreateReleaseQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      input: self.input,
+    }
+  }
+
+  pub fn as_view(&self) -> CreateReleaseQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    CreateReleaseQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+      input: self.input.as_view(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> CreateReleaseQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, input: InputRelease<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      input,
+    }
+  }
+}
@@ -0,0 +1,54 @@
+use crate::common::project::RepositoryRef;
+use crate::common::release::ReleaseId;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Delete a release
+///
+/// The Git tag of the release is kept.
+///
+/// <https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#delete-a-release>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeleteReleaseQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  pub release: ReleaseId,
+}
+
+pub type DeleteReleaseQueryView<'req, Cx> = DeleteReleaseQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> DeleteReleaseQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> DeleteReleaseQuery<NewCx, Str> {
+    DeleteReleaseQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      release: self.release,
+    }
+  }
+
+  pub fn as_view(&self) -> DeleteReleaseQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    DeleteReleaseQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+      release: self.release,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> DeleteReleaseQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, release: ReleaseId) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      release,
+    }
+  }
+}
@@ -0,0 +1,52 @@
+use crate::common::project::RepositoryRef;
+use crate::common::release::ReleaseAssetId;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Delete a release asset
+///
+/// <https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#delete-a-release-asset>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DeleteReleaseAssetQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  pub asset: ReleaseAssetId,
+}
+
+pub type DeleteReleaseAssetQueryView<'req, Cx> = DeleteReleaseAssetQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> DeleteReleaseAssetQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> DeleteReleaseAssetQuery<NewCx, Str> {
+    DeleteReleaseAssetQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      asset: self.asset,
+    }
+  }
+
+  pub fn as_view(&self) -> DeleteReleaseAssetQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    DeleteReleaseAssetQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+      asset: self.asset,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> DeleteReleaseAssetQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, asset: ReleaseAssetId) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      asset,
+    }
+  }
+}
@@ -0,0 +1,66 @@
+use crate::common::project::RepositoryRef;
+use crate::common::release::ReleaseAssetId;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Download the content of a release asset
+///
+/// The asset is requested through the API with `Accept: application/octet-stream`, so assets of private
+/// repositories can be downloaded with the query auth. GitHub redirects to a storage host: credentials are only kept
+/// for redirects on the same origin (scheme, host and port) as the API request, so they are not sent to the storage
+/// host.
+///
+/// To resume an interrupted download, send the query again with `offset` set to the number of bytes already
+/// received. Servers may ignore the range: check [`ReleaseAssetDownload::offset`](crate::common::release::ReleaseAssetDownload::offset)
+/// before appending the content.
+///
+/// <https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#get-a-release-asset>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DownloadReleaseAssetQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  pub asset: ReleaseAssetId,
+  /// Index of the first byte to download
+  pub offset: Option<u64>,
+}
+
+pub type DownloadReleaseAssetQueryView<'req, Cx> = DownloadReleaseAssetQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> DownloadReleaseAssetQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> DownloadReleaseAssetQuery<NewCx, Str> {
+    DownloadReleaseAssetQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      asset: self.asset,
+      offset: self.offset,
+    }
+  }
+
+  pub fn as_view(&self) -> DownloadReleaseAssetQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    DownloadReleaseAssetQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+      asset: self.asset,
+      offset: self.offset,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> DownloadReleaseAssetQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, asset: ReleaseAssetId) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      asset,
+      offset: None,
+    }
+  }
+}
@@ -0,0 +1,54 @@
+use crate::common::project::RepositoryRef;
+use crate::common::release::InputReleaseNotes;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Generate the name and body of release notes, listing the changes since the previous release
+///
+/// Nothing is stored: the result can be previewed, then used in [`InputRelease`](crate::common::release::InputRelease).
+///
+/// <https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#generate-release-notes-content-for-a-release>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GenerateReleaseNotesQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  pub input: InputReleaseNotes<Str>,
+}
+
+pub type GenerateReleaseNotesQueryView<'req, Cx> = GenerateReleaseNotesQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GenerateReleaseNotesQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GenerateReleaseNotesQuery<NewCx, Str> {
+    GenerateReleaseNotesQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      input: self.input,
+    }
+  }
+
+  pub fn as_view(&self) -> GenerateReleaseNotesQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GenerateReleaseNotesQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+      input: self.input.as_view(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GenerateReleaseNotesQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, input: InputReleaseNotes<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      input,
+    }
+  }
+}
@@ -0,0 +1,49 @@
+use crate::common::project::RepositoryRef;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Get the latest release of a project
+///
+/// The latest release is the most recent non-prerelease, non-draft release, sorted by `created_at`.
+///
+/// <https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#get-the-latest-release>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetProjectLatestReleaseQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+}
+
+pub type GetProjectLatestReleaseQueryView<'req, Cx> = GetProjectLatestReleaseQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetProjectLatestReleaseQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetProjectLatestReleaseQuery<NewCx, Str> {
+    GetProjectLatestReleaseQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+    }
+  }
+
+  pub fn as_view(&self) -> GetProjectLatestReleaseQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetProjectLatestReleaseQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetProjectLatestReleaseQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+    }
+  }
+}
@@ -0,0 +1,52 @@
+use crate::common::project::RepositoryRef;
+use crate::common::release::ReleaseId;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Get a project release by id
+///
+/// <https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#get-a-release>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetProjectReleaseQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  pub release: ReleaseId,
+}
+
+pub type GetProjectReleaseQueryView<'req, Cx> = GetProjectReleaseQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetProjectReleaseQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetProjectReleaseQuery<NewCx, Str> {
+    GetProjectReleaseQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      release: self.release,
+    }
+  }
+
+  pub fn as_view(&self) -> GetProjectReleaseQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetProjectReleaseQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+      release: self.release,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetProjectReleaseQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, release: ReleaseId) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      release,
+    }
+  }
+}
@@ -0,0 +1,51 @@
+use crate::common::project::RepositoryRef;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Get a project release by tag name
+///
+/// <https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#get-a-release-by-tag-name>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetProjectReleaseByTagQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  pub tag: Str,
+}
+
+pub type GetProjectReleaseByTagQueryView<'req, Cx> = GetProjectReleaseByTagQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetProjectReleaseByTagQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetProjectReleaseByTagQuery<NewCx, Str> {
+    GetProjectReleaseByTagQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      tag: self.tag,
+    }
+  }
+
+  pub fn as_view(&self) -> GetProjectReleaseByTagQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetProjectReleaseByTagQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+      tag: self.tag.as_ref(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetProjectReleaseByTagQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, tag: Str) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      tag,
+    }
+  }
+}
@@ -0,0 +1,57 @@
+use crate::common::project::RepositoryRef;
+use crate::common::release::ReleaseId;
+use crate::common::Pagination;
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// List the assets of a release
+///
+/// <https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#list-release-assets>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetReleaseAssetListQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub pagination: Option<Pagination>,
+  pub repository: RepositoryRef<Str>,
+  pub release: ReleaseId,
+}
+
+pub type GetReleaseAssetListQueryView<'req, Cx> = GetReleaseAssetListQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetReleaseAssetListQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetReleaseAssetListQuery<NewCx, Str> {
+    GetReleaseAssetListQuery {
+      context: new_context,
+      auth: self.auth,
+      pagination: self.pagination,
+      repository: self.repository,
+      release: self.release,
+    }
+  }
+
+  pub fn as_view(&self) -> GetReleaseAssetListQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetReleaseAssetListQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      pagination: self.pagination,
+      repository: self.repository.as_view(),
+      release: self.release,
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetReleaseAssetListQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, release: ReleaseId) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      pagination: None,
+      repository,
+      release,
+    }
+  }
+}
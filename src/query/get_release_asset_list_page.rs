@@ -0,0 +1,46 @@
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Get a page from the asset list of a release
+///
+/// <https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#list-release-assets>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct GetReleaseAssetListPageQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub cursor: Str,
+}
+
+pub type GetReleaseAssetListPageQueryView<'req, Cx> = GetReleaseAssetListPageQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> GetReleaseAssetListPageQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> GetReleaseAssetListPageQuery<NewCx, Str> {
+    GetReleaseAssetListPageQuery {
+      context: new_context,
+      auth: self.auth,
+      cursor: self.cursor,
+    }
+  }
+
+  pub fn as_view(&self) -> GetReleaseAssetListPageQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    GetReleaseAssetListPageQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      cursor: self.cursor.as_ref(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> GetReleaseAssetListPageQuery<EmptyContext, Str> {
+  pub const fn new(cursor: Str) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      cursor,
+    }
+  }
+}
@@ -1,2 +1,17 @@
+pub mod create_installation_access_token;
+pub mod create_release;
+pub mod delete_release;
+pub mod delete_release_asset;
+pub mod download_release_asset;
+pub mod generate_release_notes;
+pub mod get_project_latest_release;
+pub mod get_project_release;
+pub mod get_project_release_by_tag;
 pub mod get_project_release_list;
 pub mod get_project_release_list_page;
+pub mod get_release_asset;
+pub mod get_release_asset_list;
+pub mod get_release_asset_list_page;
+pub mod update_release;
+pub mod update_release_asset;
+pub mod upload_release_asset;
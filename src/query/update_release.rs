@@ -0,0 +1,56 @@
+use crate::common::project::RepositoryRef;
+use crate::common::release::{InputReleasePatch, ReleaseId};
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Update a release
+///
+/// <https://docs.github.com/en/rest/releases/releases?apiVersion=2022-11-28#update-a-release>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateReleaseQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  pub release: ReleaseId,
+  pub input: InputReleasePatch<Str>,
+}
+
+pub type UpdateReleaseQueryView<'req, Cx> = UpdateReleaseQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> UpdateReleaseQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> UpdateReleaseQuery<NewCx, Str> {
+    UpdateReleaseQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      release: self.release,
+      input: self.input,
+    }
+  }
+
+  pub fn as_view(&self) -> UpdateReleaseQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    UpdateReleaseQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+      release: self.release,
+      input: self.input.as_view(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> UpdateReleaseQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, release: ReleaseId, input: InputReleasePatch<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      release,
+      input,
+    }
+  }
+}
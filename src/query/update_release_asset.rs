@@ -0,0 +1,56 @@
+use crate::common::project::RepositoryRef;
+use crate::common::release::{InputReleaseAssetPatch, ReleaseAssetId};
+use crate::context::EmptyContext;
+use crate::GithubAuth;
+
+/// Update a release asset
+///
+/// <https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#update-a-release-asset>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UpdateReleaseAssetQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  pub asset: ReleaseAssetId,
+  pub input: InputReleaseAssetPatch<Str>,
+}
+
+pub type UpdateReleaseAssetQueryView<'req, Cx> = UpdateReleaseAssetQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> UpdateReleaseAssetQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> UpdateReleaseAssetQuery<NewCx, Str> {
+    UpdateReleaseAssetQuery {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      asset: self.asset,
+      input: self.input,
+    }
+  }
+
+  pub fn as_view(&self) -> UpdateReleaseAssetQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    UpdateReleaseAssetQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      repository: self.repository.as_view(),
+      asset: self.asset,
+      input: self.input.as_view(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> UpdateReleaseAssetQuery<EmptyContext, Str> {
+  pub const fn new(repository: RepositoryRef<Str>, asset: ReleaseAssetId, input: InputReleaseAssetPatch<Str>) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      asset,
+      input,
+    }
+  }
+}
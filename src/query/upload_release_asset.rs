@@ -0,0 +1,80 @@
+use crate::context::EmptyContext;
+use crate::url_util::UriTemplate;
+use crate::GithubAuth;
+use bytes::Bytes;
+
+/// Upload an asset to a release
+///
+/// The asset is sent to the `upload_url` of the release, which is on a separate host (`uploads.github.com` for
+/// github.com). The content is sent as a single body with a known `Content-Length`; `Bytes` can be cheaply cloned,
+/// so the request can be retried without copying the content.
+///
+/// Streaming uploads are not supported: the whole asset must be held in memory. [`HttpGithubClient`] sends every
+/// request through an inner service taking a `Request<Full<Bytes>>`, and queries are passed by reference, so the
+/// content cannot be a one-shot stream or body.
+///
+/// [`HttpGithubClient`]: crate::client::http::HttpGithubClient
+///
+/// Fails with [`Conflict`](crate::client::http::HttpGithubClientError::Conflict) if the release already has an asset
+/// with the same name.
+///
+/// <https://docs.github.com/en/rest/releases/assets?apiVersion=2022-11-28#upload-a-release-asset>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UploadReleaseAssetQuery<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  /// `upload_url` of the release
+  pub upload_url: UriTemplate,
+  pub name: Str,
+  pub label: Option<Str>,
+  /// Media type of the asset, e.g. `application/gzip`
+  pub content_type: Str,
+  /// Full content of the asset, buffered in memory
+  pub content: Bytes,
+}
+
+pub type UploadReleaseAssetQueryView<'req, Cx> = UploadReleaseAssetQuery<&'req Cx, &'req str>;
+
+impl<Cx, Str> UploadReleaseAssetQuery<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> UploadReleaseAssetQuery<NewCx, Str> {
+    UploadReleaseAssetQuery {
+      context: new_context,
+      auth: self.auth,
+      upload_url: self.upload_url,
+      name: self.name,
+      label: self.label,
+      content_type: self.content_type,
+      content: self.content,
+    }
+  }
+
+  pub fn as_view(&self) -> UploadReleaseAssetQueryView<'_, Cx>
+  where
+    Str: AsRef<str>,
+  {
+    UploadReleaseAssetQueryView {
+      context: &self.context,
+      auth: self.auth.as_ref().map(GithubAuth::as_view),
+      upload_url: self.upload_url.clone(),
+      name: self.name.as_ref(),
+      label: self.label.as_ref().map(|label| label.as_ref()),
+      content_type: self.content_type.as_ref(),
+      content: self.content.clone(),
+    }
+  }
+}
+
+impl<Str: AsRef<str>> UploadReleaseAssetQuery<EmptyContext, Str> {
+  pub const fn new(upload_url: UriTemplate, name: Str, content_type: Str, content: Bytes) -> Self {
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      upload_url,
+      name,
+      label: None,
+      content_type,
+      content,
+    }
+  }
+}
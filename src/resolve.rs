@@ -0,0 +1,212 @@
+//! Selection of a release by semantic version
+
+use crate::common::release::Release;
+use crate::paginate::{PageSource, Paginate, PaginatedQuery};
+use compact_str::CompactString;
+use futures::TryStreamExt;
+use semver::{Op, Version, VersionReq};
+
+/// Criteria to select a release by version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseRequirement {
+  pub version: VersionReq,
+  /// Prefixes stripped from tag names before parsing them as versions, tried in order.
+  ///
+  /// Tags without any of these prefixes are ignored; add an empty prefix to accept bare versions.
+  pub tag_prefixes: Vec<CompactString>,
+  /// Accept prereleases, either flagged as such on GitHub or with a prerelease version (e.g. `1.5.0-rc.1`)
+  pub prereleases: bool,
+  /// Accept draft releases, only visible with push access to the repository
+  pub drafts: bool,
+}
+
+impl ReleaseRequirement {
+  /// Requirement on tags such as `v1.2.3` or `1.2.3`, excluding drafts and prereleases
+  pub fn new(version: VersionReq) -> Self {
+    Self {
+      version,
+      tag_prefixes: vec![CompactString::new("v"), CompactString::new("")],
+      prereleases: false,
+      drafts: false,
+    }
+  }
+
+  /// Version of a tag, if it has one of the prefixes
+  pub fn parse_tag(&self, tag: &str) -> Option<Version> {
+    self
+      .tag_prefixes
+      .iter()
+      .filter_map(|prefix| tag.strip_prefix(prefix.as_str()))
+      .find_map(|version| Version::parse(version).ok())
+  }
+
+  /// Version of `release`, if it satisfies this requirement
+  pub fn matches(&self, release: &Release) -> Option<Version> {
+    if release.draft && !self.drafts {
+      return None;
+    }
+    let version = self.parse_tag(&release.tag_name)?;
+    let is_prerelease = release.prerelease || !version.pre.is_empty();
+    if is_prerelease && !self.prereleases {
+      return None;
+    }
+    // `VersionReq` only matches prereleases of the exact versions it mentions: compare the release version instead
+    let release_version = Version {
+      pre: semver::Prerelease::EMPTY,
+      ..version.clone()
+    };
+    (self.version.matches(&version) || (self.prereleases && self.version.matches(&release_version))).then_some(version)
+  }
+
+  /// Whether no version greater than `version` can satisfy this requirement
+  fn is_highest(&self, version: &Version) -> bool {
+    self.version.comparators.iter().any(|comparator| {
+      comparator.op == Op::Exact
+        && comparator.major == version.major
+        && comparator.minor == Some(version.minor)
+        && comparator.patch == Some(version.patch)
+        && comparator.pre == version.pre
+    })
+  }
+}
+
+/// Find the release with the highest version satisfying `requirement`, along with its version.
+///
+/// The releases are listed with `query`, typically a
+/// [`GetProjectReleaseListQuery`](crate::query::get_project_release_list::GetProjectReleaseListQuery), sent with
+/// `client`: a `&mut` reference to a client implementing the query services, or a [`GithubClient`](crate::GithubClient)
+/// wrapped in [`GithubClientPages`](crate::paginate::GithubClientPages). GitHub lists
+/// releases by creation date rather than by version, so all the pages are read unless the requirement pins an exact
+/// version: the pagination then stops at the first match.
+pub async fn resolve_release<S, Q>(
+  client: S,
+  query: Q,
+  requirement: &ReleaseRequirement,
+) -> Result<Option<(Version, Release)>, S::Error>
+where
+  Q: PaginatedQuery<Item = Release>,
+  S: PageSource<Q>,
+{
+  let mut releases = core::pin::pin!(Paginate::new(client, query).items());
+  let mut best: Option<(Version, Release)> = None;
+  while let Some(release) = releases.try_next().await? {
+    let Some(version) = requirement.matches(&release) else {
+      continue;
+    };
+    if best.as_ref().is_some_and(|(best, _)| *best >= version) {
+      continue;
+    }
+    let is_highest = requirement.is_highest(&version);
+    best = Some((version, release));
+    if is_highest {
+      break;
+    }
+  }
+  Ok(best)
+}
+
+#[cfg(all(test, feature = "fake"))]
+mod test {
+  use super::*;
+  use crate::common::project::RepositoryRef;
+  use crate::common::Pagination;
+  use crate::fake::FakeGithub;
+  use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+  use std::num::NonZeroU8;
+  use tower_service::Service;
+
+  fn hello_world() -> (
+    FakeGithub,
+    GetProjectReleaseListQuery<crate::context::EmptyContext, CompactString>,
+  ) {
+    let github = FakeGithub::new();
+    let id = github
+      .load_releases(
+        "octocat",
+        "Hello-World",
+        "./test-resources/fake/octocat-hello-world/releases.json",
+      )
+      .unwrap();
+    let mut query = GetProjectReleaseListQuery::new(RepositoryRef::Id(id));
+    query.pagination = Some(Pagination {
+      per_page: NonZeroU8::new(1),
+    });
+    (github, query)
+  }
+
+  async fn resolve(requirement: &ReleaseRequirement) -> Option<String> {
+    let (mut github, query) = hello_world();
+    resolve_release(&mut github, query, requirement)
+      .await
+      .unwrap()
+      .map(|(_, release)| release.tag_name)
+  }
+
+  #[tokio::test]
+  async fn resolve_highest_matching_release() {
+    let mut requirement = ReleaseRequirement::new(VersionReq::parse("~1.1").unwrap());
+    assert_eq!(resolve(&requirement).await.as_deref(), Some("v1.1.0"));
+    requirement.version = VersionReq::parse("^2").unwrap();
+    assert_eq!(resolve(&requirement).await, None);
+    requirement.tag_prefixes = vec![CompactString::new("cli-v")];
+    requirement.version = VersionReq::STAR;
+    assert_eq!(resolve(&requirement).await, None);
+  }
+
+  #[tokio::test]
+  async fn exclude_prereleases_unless_requested() {
+    let mut requirement = ReleaseRequirement::new(VersionReq::parse("=1.1.0-rc.1").unwrap());
+    assert_eq!(resolve(&requirement).await, None);
+    requirement.prereleases = true;
+    assert_eq!(resolve(&requirement).await.as_deref(), Some("v1.1.0-rc.1"));
+  }
+
+  /// Service counting the queries sent to the inner service
+  struct Counting<S> {
+    inner: S,
+    calls: usize,
+  }
+
+  impl<'q, S, Q> Service<&'q Q> for Counting<S>
+  where
+    S: Service<&'q Q>,
+  {
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut core::task::Context<'_>) -> core::task::Poll<Result<(), Self::Error>> {
+      self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: &'q Q) -> Self::Future {
+      self.calls += 1;
+      self.inner.call(req)
+    }
+  }
+
+  #[tokio::test]
+  async fn stop_at_exact_version() {
+    let (github, query) = hello_world();
+    let mut client = Counting {
+      inner: github,
+      calls: 0,
+    };
+    let requirement = ReleaseRequirement::new(VersionReq::parse("=1.2.0").unwrap());
+    let (version, _) = resolve_release(&mut client, query.clone(), &requirement)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(version, Version::new(1, 2, 0));
+    assert_eq!(client.calls, 1);
+
+    client.calls = 0;
+    let requirement = ReleaseRequirement::new(VersionReq::parse("^1").unwrap());
+    let (version, _) = resolve_release(&mut client, query, &requirement)
+      .await
+      .unwrap()
+      .unwrap();
+    assert_eq!(version, Version::new(1, 2, 0));
+    assert_eq!(client.calls, 4);
+  }
+}
@@ -0,0 +1,581 @@
+//! Self-update of programs distributed as GitHub release assets
+
+use crate::common::digest::DigestError;
+use crate::common::project::{ProjectSlug, RepositoryRef};
+use crate::common::release::{Release, ReleaseAsset, ReleaseAssetDownload};
+use crate::common::Pagination;
+use crate::context::EmptyContext;
+use crate::paginate::GithubClientPages;
+use crate::platform::{AssetMatchError, AssetMatcher};
+use crate::query::download_release_asset::DownloadReleaseAssetQuery;
+use crate::query::get_project_release_list::GetProjectReleaseListQuery;
+use crate::resolve::{resolve_release, ReleaseRequirement};
+use crate::verify::{download_verified, VerifyError};
+use crate::{GithubAuth, GithubClient};
+use compact_str::CompactString;
+use futures::TryStreamExt;
+use semver::{Comparator, Op, Version, VersionReq};
+use std::ffi::OsString;
+use std::fs;
+use std::io::{self, Read, Seek, Write};
+use std::num::NonZeroU8;
+use std::path::{Path, PathBuf};
+
+/// Compressed formats that cannot be unpacked, to avoid installing them as executables
+const UNSUPPORTED_ARCHIVES: [&str; 10] = [
+  ".tar.xz", ".txz", ".tar.zst", ".tar.bz2", ".tbz2", ".xz", ".zst", ".bz2", ".gz", ".7z",
+];
+
+#[derive(Debug, thiserror::Error)]
+pub enum SelfUpdateError<E> {
+  #[error("failed to list releases")]
+  ListReleases(#[source] E),
+  #[error(transparent)]
+  Asset(#[from] AssetMatchError),
+  #[error("failed to download asset")]
+  Download(#[source] E),
+  #[error("failed to verify asset")]
+  Verify(#[source] VerifyError<E>),
+  #[error("failed to read asset content")]
+  ReadAsset(#[source] io::Error),
+  #[error("failed to store the downloaded asset")]
+  StoreAsset(#[source] io::Error),
+  #[error("unsupported archive format: {0:?}")]
+  UnsupportedArchive(String),
+  #[error("failed to extract archive")]
+  Extract(#[source] io::Error),
+  #[error("archive does not contain the executable {0:?}")]
+  MissingExecutable(CompactString),
+  #[error("failed to replace the executable")]
+  Install(#[source] io::Error),
+}
+
+/// Release selected by [`SelfUpdate::check`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Update {
+  pub version: Version,
+  pub release: Release,
+  /// Asset built for the target platform
+  pub asset: ReleaseAsset,
+}
+
+/// Update of a program from the releases of its repository
+///
+/// The highest release satisfying `requirement` and newer than `current_version` is selected, then its asset for the
+/// target platform is downloaded and verified (see [`download_verified`]). Assets are unpacked if they are `.tar`,
+/// `.tar.gz` or `.zip` archives, and used as is otherwise; assets in other compressed formats (e.g. `.tar.xz`) are
+/// never selected. The download is streamed to a temporary file in the directory of `install_path`, then the
+/// executable at `install_path` is replaced with [`replace_executable`].
+///
+/// Releases are listed and downloaded through a [`GithubClient`], with owned strings in the queries. As its errors may
+/// borrow the queries, the context type must be `'static` (e.g. [`Context`](crate::context::Context)).
+#[derive(Debug, Clone)]
+pub struct SelfUpdate<Cx, Str> {
+  pub context: Cx,
+  pub auth: Option<GithubAuth<Str>>,
+  pub repository: RepositoryRef<Str>,
+  /// Version of the installed program
+  pub current_version: Version,
+  /// Releases to consider; by default, releases semver-compatible with `current_version`
+  pub requirement: ReleaseRequirement,
+  pub matcher: AssetMatcher,
+  /// Path of the executable to replace
+  pub install_path: PathBuf,
+  /// File name of the executable in archives; the file name of `install_path` by default
+  pub executable_name: CompactString,
+  /// Install assets without digest nor checksum manifest, instead of failing with [`VerifyError::MissingDigest`]
+  pub allow_unverified: bool,
+}
+
+impl<Cx, Str> SelfUpdate<Cx, Str> {
+  pub fn set_context<NewCx>(self, new_context: NewCx) -> SelfUpdate<NewCx, Str> {
+    SelfUpdate {
+      context: new_context,
+      auth: self.auth,
+      repository: self.repository,
+      current_version: self.current_version,
+      requirement: self.requirement,
+      matcher: self.matcher,
+      install_path: self.install_path,
+      executable_name: self.executable_name,
+      allow_unverified: self.allow_unverified,
+    }
+  }
+
+  /// Find the release to update to, if any.
+  ///
+  /// Only assets that [`SelfUpdate::install`] can unpack are considered.
+  pub async fn check<C, E>(&self, client: &mut C) -> Result<Option<Update>, SelfUpdateError<E>>
+  where
+    Cx: Clone,
+    Str: AsRef<str>,
+    for<'a> &'a mut C: GithubClient<Cx, CompactString>,
+    for<'a, 'r> <&'a mut C as GithubClient<Cx, CompactString>>::GetProjectReleaseListError<'r>: Into<E>,
+    for<'a, 'r> <&'a mut C as GithubClient<Cx, CompactString>>::GetProjectReleaseListPageError<'r>: Into<E>,
+  {
+    let query = GetProjectReleaseListQuery {
+      context: self.context.clone(),
+      auth: self.query_auth(),
+      pagination: Some(Pagination {
+        per_page: NonZeroU8::new(100),
+      }),
+      repository: self.query_repository(),
+    };
+    let resolved = resolve_release(GithubClientPages::new(client), query, &self.requirement)
+      .await
+      .map_err(SelfUpdateError::ListReleases)?;
+    let Some((version, release)) = resolved.filter(|(version, _)| *version > self.current_version) else {
+      return Ok(None);
+    };
+    let installable: Vec<ReleaseAsset> = release
+      .assets
+      .iter()
+      .filter(|asset| AssetFormat::from_name(&asset.name).is_some())
+      .cloned()
+      .collect();
+    let asset = self.matcher.select_from(&installable)?.clone();
+    Ok(Some(Update {
+      version,
+      release,
+      asset,
+    }))
+  }
+
+  /// Download the asset of `update` and install it at `install_path`.
+  ///
+  /// The executable is left untouched if any step fails.
+  pub async fn install<C, E>(&self, client: &mut C, update: &Update) -> Result<(), SelfUpdateError<E>>
+  where
+    Cx: Clone,
+    Str: AsRef<str>,
+    for<'a> &'a mut C: GithubClient<Cx, CompactString>,
+    for<'a, 'r> <&'a mut C as GithubClient<Cx, CompactString>>::DownloadReleaseAssetError<'r>: Into<E>,
+  {
+    let query = DownloadReleaseAssetQuery {
+      context: self.context.clone(),
+      auth: self.query_auth(),
+      repository: self.query_repository(),
+      asset: update.asset.id,
+      offset: None,
+    };
+    let download = match download_verified(client, &update.release, &query).await {
+      Ok(download) => download,
+      Err(VerifyError::MissingDigest(_)) if self.allow_unverified => client
+        .download_release_asset(&query)
+        .await
+        .map_err(|e| SelfUpdateError::Download(e.into()))?,
+      Err(VerifyError::Download(e)) => return Err(SelfUpdateError::Download(e)),
+      Err(e) => return Err(SelfUpdateError::Verify(e)),
+    };
+    let dir = self
+      .install_path
+      .parent()
+      .filter(|dir| !dir.as_os_str().is_empty())
+      .unwrap_or(Path::new("."));
+    let file = download_to_file(download, dir).await?;
+    let executable = extract_executable(&update.asset.name, file, &self.executable_name)?;
+    replace_executable(&self.install_path, &executable).map_err(SelfUpdateError::Install)
+  }
+
+  /// Check for an update and install it, returning the installed version.
+  pub async fn update<C, E>(&self, client: &mut C) -> Result<Option<Version>, SelfUpdateError<E>>
+  where
+    Cx: Clone,
+    Str: AsRef<str>,
+    for<'a> &'a mut C: GithubClient<Cx, CompactString>,
+    for<'a, 'r> <&'a mut C as GithubClient<Cx, CompactString>>::GetProjectReleaseListError<'r>: Into<E>,
+    for<'a, 'r> <&'a mut C as GithubClient<Cx, CompactString>>::GetProjectReleaseListPageError<'r>: Into<E>,
+    for<'a, 'r> <&'a mut C as GithubClient<Cx, CompactString>>::DownloadReleaseAssetError<'r>: Into<E>,
+  {
+    let Some(update) = self.check(client).await? else {
+      return Ok(None);
+    };
+    self.install(client, &update).await?;
+    Ok(Some(update.version))
+  }
+
+  /// Auth of the queries, with owned strings
+  fn query_auth(&self) -> Option<GithubAuth>
+  where
+    Str: AsRef<str>,
+  {
+    self.auth.as_ref().map(|auth| match auth.as_view() {
+      GithubAuth::Bearer(token) => GithubAuth::Bearer(CompactString::new(token)),
+      GithubAuth::Token(token) => GithubAuth::Token(CompactString::new(token)),
+      GithubAuth::Basic {
+        client_id,
+        client_secret,
+      } => GithubAuth::Basic {
+        client_id: CompactString::new(client_id),
+        client_secret: CompactString::new(client_secret),
+      },
+    })
+  }
+
+  /// Repository of the queries, with owned strings
+  fn query_repository(&self) -> RepositoryRef
+  where
+    Str: AsRef<str>,
+  {
+    match self.repository.as_view() {
+      RepositoryRef::Id(id) => RepositoryRef::Id(id),
+      RepositoryRef::Slug(slug) => RepositoryRef::Slug(ProjectSlug::new(
+        CompactString::new(slug.owner),
+        CompactString::new(slug.name),
+      )),
+    }
+  }
+}
+
+impl<Str> SelfUpdate<EmptyContext, Str> {
+  /// Update of the executable at `install_path`, for the current platform
+  pub fn new(repository: RepositoryRef<Str>, current_version: Version, install_path: PathBuf) -> Self {
+    let requirement = VersionReq {
+      comparators: vec![Comparator {
+        op: Op::Caret,
+        major: current_version.major,
+        minor: Some(current_version.minor),
+        patch: Some(current_version.patch),
+        pre: current_version.pre.clone(),
+      }],
+    };
+    let executable_name = install_path
+      .file_name()
+      .map(|name| CompactString::new(name.to_string_lossy()))
+      .unwrap_or_default();
+    Self {
+      context: EmptyContext::new(),
+      auth: None,
+      repository,
+      current_version,
+      requirement: ReleaseRequirement::new(requirement),
+      matcher: AssetMatcher::current(),
+      install_path,
+      executable_name,
+      allow_unverified: false,
+    }
+  }
+
+  /// Update of the running executable, for the current platform
+  pub fn current_exe(repository: RepositoryRef<Str>, current_version: Version) -> io::Result<Self> {
+    Ok(Self::new(repository, current_version, std::env::current_exe()?))
+  }
+}
+
+/// Stream the content of `download` to an anonymous temporary file in `dir`, rewound for reading
+async fn download_to_file<E>(download: ReleaseAssetDownload, dir: &Path) -> Result<fs::File, SelfUpdateError<E>> {
+  let mut file = tempfile::tempfile_in(dir).map_err(SelfUpdateError::StoreAsset)?;
+  let mut chunks = download.content;
+  while let Some(chunk) = chunks.try_next().await.map_err(read_error)? {
+    file.write_all(&chunk).map_err(SelfUpdateError::StoreAsset)?;
+  }
+  file.rewind().map_err(SelfUpdateError::StoreAsset)?;
+  Ok(file)
+}
+
+/// Report the digest mismatches detected while streaming as verification errors
+fn read_error<E>(e: io::Error) -> SelfUpdateError<E> {
+  match e.get_ref().and_then(|inner| inner.downcast_ref::<DigestError>()) {
+    Some(digest) => SelfUpdateError::Verify(VerifyError::Digest(digest.clone())),
+    None => SelfUpdateError::ReadAsset(e),
+  }
+}
+
+/// Format of an asset that can be installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum AssetFormat {
+  TarGz,
+  Tar,
+  Zip,
+  Executable,
+}
+
+impl AssetFormat {
+  /// Format of the asset `name`; `None` for compressed formats that cannot be unpacked
+  fn from_name(name: &str) -> Option<Self> {
+    let name = name.to_ascii_lowercase();
+    if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+      Some(Self::TarGz)
+    } else if name.ends_with(".tar") {
+      Some(Self::Tar)
+    } else if name.ends_with(".zip") {
+      Some(Self::Zip)
+    } else if UNSUPPORTED_ARCHIVES.iter().any(|ext| name.ends_with(ext)) {
+      None
+    } else {
+      Some(Self::Executable)
+    }
+  }
+}
+
+/// Content of the executable, unpacked from the asset `asset_name` if it is an archive
+fn extract_executable<E>(
+  asset_name: &str,
+  mut file: fs::File,
+  executable: &str,
+) -> Result<Vec<u8>, SelfUpdateError<E>> {
+  let format =
+    AssetFormat::from_name(asset_name).ok_or_else(|| SelfUpdateError::UnsupportedArchive(asset_name.to_string()))?;
+  let extracted = match format {
+    AssetFormat::TarGz => extract_tar(flate2::read::GzDecoder::new(io::BufReader::new(file)), executable),
+    AssetFormat::Tar => extract_tar(io::BufReader::new(file), executable),
+    AssetFormat::Zip => extract_zip(io::BufReader::new(file), executable),
+    AssetFormat::Executable => {
+      let mut content = Vec::new();
+      file.read_to_end(&mut content).map_err(SelfUpdateError::ReadAsset)?;
+      return Ok(content);
+    }
+  };
+  extracted
+    .map_err(SelfUpdateError::Extract)?
+    .ok_or_else(|| SelfUpdateError::MissingExecutable(CompactString::new(executable)))
+}
+
+/// Content of the first regular file named `executable`, in any directory of a tar archive
+fn extract_tar(archive: impl Read, executable: &str) -> io::Result<Option<Vec<u8>>> {
+  let mut archive = tar::Archive::new(archive);
+  for entry in archive.entries()? {
+    let mut entry = entry?;
+    if entry.header().entry_type().is_file() && entry.path()?.file_name().is_some_and(|name| name == executable) {
+      let mut content = Vec::new();
+      entry.read_to_end(&mut content)?;
+      return Ok(Some(content));
+    }
+  }
+  Ok(None)
+}
+
+/// Content of the first regular file named `executable`, in any directory of a zip archive
+fn extract_zip(archive: impl Read + Seek, executable: &str) -> io::Result<Option<Vec<u8>>> {
+  let mut archive = zip::ZipArchive::new(archive)?;
+  for index in 0..archive.len() {
+    let mut file = archive.by_index(index)?;
+    let is_executable = file
+      .enclosed_name()
+      .is_some_and(|path| path.file_name().is_some_and(|name| name == executable));
+    if file.is_file() && is_executable {
+      let mut content = Vec::new();
+      file.read_to_end(&mut content)?;
+      return Ok(Some(content));
+    }
+  }
+  Ok(None)
+}
+
+/// Replace the executable at `path` with `content`, keeping its permissions.
+///
+/// The new executable is fully written next to `path` before being renamed over it, so `path` always holds a
+/// complete executable. On Windows, running executables cannot be overwritten: the previous one is first moved
+/// aside, and moved back if the new one cannot be put in place. Being still in use, it is only removed by the next
+/// update.
+pub fn replace_executable(path: &Path, content: &[u8]) -> io::Result<()> {
+  let file_name = path
+    .file_name()
+    .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "executable path has no file name"))?;
+  let sibling = |suffix: &str| {
+    let mut name = OsString::from(".");
+    name.push(file_name);
+    name.push(suffix);
+    path.with_file_name(name)
+  };
+  let new_path = sibling(".new");
+  let backup_path = sibling(".old");
+  // Left over by a previous update on Windows
+  let _ = fs::remove_file(&backup_path);
+
+  let permissions = fs::metadata(path)?.permissions();
+  let written = fs::File::create(&new_path).and_then(|mut file| {
+    file.write_all(content)?;
+    file.set_permissions(permissions)?;
+    file.sync_all()
+  });
+  if let Err(e) = written {
+    let _ = fs::remove_file(&new_path);
+    return Err(e);
+  }
+  swap_executable(&new_path, path, cfg!(windows).then_some(backup_path.as_path()))
+}
+
+/// Move `new_path` to `path`, moving the previous file to `backup_path` first if set.
+///
+/// On failure, the previous file is restored and `new_path` is removed.
+fn swap_executable(new_path: &Path, path: &Path, backup_path: Option<&Path>) -> io::Result<()> {
+  if let Some(backup_path) = backup_path {
+    if let Err(e) = fs::rename(path, backup_path) {
+      let _ = fs::remove_file(new_path);
+      return Err(e);
+    }
+  }
+  if let Err(e) = fs::rename(new_path, path) {
+    if let Some(backup_path) = backup_path {
+      let _ = fs::rename(backup_path, path);
+    }
+    let _ = fs::remove_file(new_path);
+    return Err(e);
+  }
+  if let Some(backup_path) = backup_path {
+    let _ = fs::remove_file(backup_path);
+  }
+  Ok(())
+}
+
+#[cfg(all(test, feature = "fake"))]
+mod test {
+  use super::*;
+  use crate::client::http::HttpGithubClientError;
+  use crate::common::project::ProjectId;
+  use crate::fake::FakeGithub;
+  use crate::platform::Target;
+  use bytes::Bytes;
+
+  const ASSET: &str = "tool-x86_64-unknown-linux-gnu.tar.gz";
+
+  fn tar_gz(path: &str, content: &[u8]) -> Vec<u8> {
+    let encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o755);
+    header.set_cksum();
+    builder.append_data(&mut header, path, content).unwrap();
+    builder.into_inner().unwrap().finish().unwrap()
+  }
+
+  fn zip(path: &str, content: &[u8]) -> Vec<u8> {
+    let mut writer = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+    writer
+      .start_file(path, zip::write::SimpleFileOptions::default())
+      .unwrap();
+    writer.write_all(content).unwrap();
+    writer.finish().unwrap().into_inner()
+  }
+
+  fn installed(dir: &Path) -> (PathBuf, SelfUpdate<EmptyContext, CompactString>, ProjectId, FakeGithub) {
+    let github = FakeGithub::new();
+    let repository = github.add_repository("octocat", "tool");
+    let install_path = dir.join("tool");
+    fs::write(&install_path, b"tool 1.0.0").unwrap();
+    let mut update = SelfUpdate::new(
+      RepositoryRef::Id(repository),
+      Version::new(1, 0, 0),
+      install_path.clone(),
+    );
+    update.matcher = AssetMatcher::new(Target::from_triple("x86_64-unknown-linux-gnu").unwrap());
+    (install_path, update, repository, github)
+  }
+
+  #[tokio::test]
+  async fn update_to_latest_compatible_release() {
+    let dir = tempfile::tempdir().unwrap();
+    let (install_path, mut update, repository, mut github) = installed(dir.path());
+    #[cfg(unix)]
+    fs::set_permissions(&install_path, std::os::unix::fs::PermissionsExt::from_mode(0o750)).unwrap();
+    github.publish_release(
+      repository,
+      "v1.1.0",
+      &[(ASSET, &tar_gz("tool-1.1.0/tool", b"tool 1.1.0"))],
+    );
+    github.publish_release(
+      repository,
+      "v2.0.0",
+      &[(ASSET, &tar_gz("tool-2.0.0/tool", b"tool 2.0.0"))],
+    );
+
+    assert_eq!(
+      update.update::<_, HttpGithubClientError>(&mut github).await.unwrap(),
+      Some(Version::new(1, 1, 0))
+    );
+    assert_eq!(fs::read(&install_path).unwrap(), b"tool 1.1.0");
+    #[cfg(unix)]
+    assert_eq!(
+      std::os::unix::fs::PermissionsExt::mode(&fs::metadata(&install_path).unwrap().permissions()) & 0o777,
+      0o750
+    );
+    let files: Vec<_> = fs::read_dir(dir.path())
+      .unwrap()
+      .map(|entry| entry.unwrap().file_name())
+      .collect();
+    assert_eq!(files, ["tool"]);
+
+    update.current_version = Version::new(1, 1, 0);
+    assert_eq!(
+      update.update::<_, HttpGithubClientError>(&mut github).await.unwrap(),
+      None
+    );
+    update.requirement.version = VersionReq::STAR;
+    assert_eq!(
+      update.update::<_, HttpGithubClientError>(&mut github).await.unwrap(),
+      Some(Version::new(2, 0, 0))
+    );
+    assert_eq!(fs::read(&install_path).unwrap(), b"tool 2.0.0");
+  }
+
+  #[tokio::test]
+  async fn skip_assets_that_cannot_be_unpacked() {
+    let dir = tempfile::tempdir().unwrap();
+    let (install_path, update, repository, mut github) = installed(dir.path());
+    github.publish_release(
+      repository,
+      "v1.1.0",
+      &[
+        ("tool-x86_64-unknown-linux-gnu.tar.zst", b"zstd"),
+        ("tool-x86_64-unknown-linux-gnu", b"tool 1.1.0"),
+      ],
+    );
+
+    let found = update.check::<_, HttpGithubClientError>(&mut github).await.unwrap();
+    assert_eq!(
+      found.map(|found| found.asset.name),
+      Some(String::from("tool-x86_64-unknown-linux-gnu"))
+    );
+    assert_eq!(
+      update.update::<_, HttpGithubClientError>(&mut github).await.unwrap(),
+      Some(Version::new(1, 1, 0))
+    );
+    assert_eq!(fs::read(&install_path).unwrap(), b"tool 1.1.0");
+  }
+
+  #[tokio::test]
+  async fn keep_executable_on_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let (install_path, update, repository, mut github) = installed(dir.path());
+    let asset = "tool-x86_64-unknown-linux-gnu.zip";
+    let release = github.publish_release(repository, "v1.1.0", &[(asset, &zip("bin/other", b"other"))]);
+
+    assert!(matches!(
+      update.update::<_, HttpGithubClientError>(&mut github).await,
+      Err(SelfUpdateError::MissingExecutable(name)) if name == "tool"
+    ));
+    github.set_asset_content(release.assets[0].id, Bytes::from(zip("bin/tool", b"tool 1.1.0")));
+    assert!(matches!(
+      update.update::<_, HttpGithubClientError>(&mut github).await,
+      Err(SelfUpdateError::Verify(VerifyError::Digest(
+        DigestError::Mismatch { .. }
+      )))
+    ));
+    assert_eq!(fs::read(&install_path).unwrap(), b"tool 1.0.0");
+    let files: Vec<_> = fs::read_dir(dir.path())
+      .unwrap()
+      .map(|entry| entry.unwrap().file_name())
+      .collect();
+    assert_eq!(files, ["tool"]);
+  }
+
+  #[test]
+  fn restore_backup_on_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("tool");
+    let backup_path = dir.path().join(".tool.old");
+    fs::write(&path, b"tool 1.0.0").unwrap();
+    let new_path = dir.path().join(".tool.new");
+    fs::write(&new_path, b"tool 1.1.0").unwrap();
+    swap_executable(&new_path, &path, Some(&backup_path)).unwrap();
+    assert_eq!(fs::read(&path).unwrap(), b"tool 1.1.0");
+    assert!(!backup_path.exists());
+
+    // The new executable is missing: the rename fails after the previous one was moved aside
+    assert!(swap_executable(&new_path, &path, Some(&backup_path)).is_err());
+    assert_eq!(fs::read(&path).unwrap(), b"tool 1.1.0");
+    assert!(!backup_path.exists());
+  }
+}
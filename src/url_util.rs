@@ -1,3 +1,6 @@
+use compact_str::CompactString;
+use core::fmt;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use url::Url;
 
 pub fn url_join<I>(url: &Url, segments: I) -> Url
@@ -29,3 +32,120 @@ impl UrlExt for Url {
     url_join(self, segments)
   }
 }
+
+/// Characters kept as-is when expanding a template variable (RFC 3986 "unreserved" set)
+const UNRESERVED: &AsciiSet = &NON_ALPHANUMERIC.remove(b'-').remove(b'.').remove(b'_').remove(b'~');
+
+/// RFC 6570 URI template, such as the `upload_url` of a release (`.../assets{?name,label}`)
+///
+/// Only the expressions used by GitHub are supported: simple string expansion (`{var}`), form-style query
+/// expansion (`{?var}`) and query continuation (`{&var}`).
+///
+/// See <https://www.rfc-editor.org/rfc/rfc6570>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UriTemplate(CompactString);
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum UriTemplateError {
+  #[error("unterminated template expression")]
+  Unterminated,
+  #[error("unsupported template operator {0:?}")]
+  UnsupportedOperator(char),
+  #[error("expanded template is not a valid URL: {0}")]
+  Url(#[from] url::ParseError),
+}
+
+impl UriTemplate {
+  pub fn new(template: impl Into<CompactString>) -> Self {
+    Self(template.into())
+  }
+
+  pub fn as_str(&self) -> &str {
+    self.0.as_str()
+  }
+
+  /// Expand the template; variables missing from `vars` or set to `None` are undefined and omitted.
+  pub fn expand(&self, vars: &[(&str, Option<&str>)]) -> Result<Url, UriTemplateError> {
+    let lookup = |name: &str| vars.iter().find(|(var, _)| *var == name).and_then(|(_, value)| *value);
+    let mut out = String::with_capacity(self.0.len());
+    let mut rest = self.0.as_str();
+    while let Some(start) = rest.find('{') {
+      out.push_str(&rest[..start]);
+      let end = rest[start..].find('}').ok_or(UriTemplateError::Unterminated)? + start;
+      let expression = &rest[start + 1..end];
+      let (operator, names) = match expression.chars().next() {
+        Some(op @ ('?' | '&')) => (Some(op), &expression[1..]),
+        Some(op) if !(op.is_ascii_alphanumeric() || op == '_') => {
+          return Err(UriTemplateError::UnsupportedOperator(op))
+        }
+        _ => (None, expression),
+      };
+      let mut first = true;
+      for name in names.split(',') {
+        let Some(value) = lookup(name) else {
+          continue;
+        };
+        let value = utf8_percent_encode(value, UNRESERVED);
+        match operator {
+          None => {
+            if !first {
+              out.push(',');
+            }
+            out.extend(value);
+          }
+          Some(op) => {
+            out.push(if first && op == '?' { '?' } else { '&' });
+            out.push_str(name);
+            out.push('=');
+            out.extend(value);
+          }
+        }
+        first = false;
+      }
+      rest = &rest[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(Url::parse(&out)?)
+  }
+}
+
+impl fmt::Display for UriTemplate {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn expand_upload_url() {
+    let template =
+      UriTemplate::new("https://uploads.github.com/repos/octocat/Hello-World/releases/1/assets{?name,label}");
+    assert_eq!(
+      template
+        .expand(&[("name", Some("hello world.tar.gz")), ("label", None)])
+        .unwrap()
+        .as_str(),
+      "https://uploads.github.com/repos/octocat/Hello-World/releases/1/assets?name=hello%20world.tar.gz"
+    );
+    assert_eq!(
+      template
+        .expand(&[("name", Some("a+b")), ("label", Some("Linux (x64)"))])
+        .unwrap()
+        .as_str(),
+      "https://uploads.github.com/repos/octocat/Hello-World/releases/1/assets?name=a%2Bb&label=Linux%20%28x64%29"
+    );
+  }
+
+  #[test]
+  fn reject_unterminated_expression() {
+    assert_eq!(
+      UriTemplate::new("https://example.com/{?name").expand(&[]),
+      Err(UriTemplateError::Unterminated)
+    );
+  }
+}
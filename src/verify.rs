@@ -0,0 +1,219 @@
+//! Checksum verification of downloaded release assets
+
+use crate::common::digest::{ChecksumManifest, Digest, DigestError};
+use crate::common::release::{Release, ReleaseAssetDownload, ReleaseAssetId};
+use crate::query::download_release_asset::DownloadReleaseAssetQuery;
+use crate::GithubClient;
+use futures::{stream, StreamExt, TryStreamExt};
+use std::io;
+
+/// Maximum size of a checksum manifest, to avoid buffering unexpectedly large assets
+const MAX_MANIFEST_SIZE: u64 = 1024 * 1024;
+
+#[derive(Debug, thiserror::Error)]
+pub enum VerifyError<E> {
+  #[error("failed to download asset")]
+  Download(#[source] E),
+  #[error("failed to read checksum manifest")]
+  ReadManifest(#[source] io::Error),
+  #[error("asset {} is not part of the release", .0.into_u64())]
+  UnknownAsset(ReleaseAssetId),
+  #[error("no digest available for asset {0:?}")]
+  MissingDigest(String),
+  #[error(transparent)]
+  Digest(#[from] DigestError),
+}
+
+/// Check the content of `download` against `expected` while it is streamed.
+///
+/// The returned download yields the same chunks, but fails with an [`io::ErrorKind::InvalidData`] error wrapping
+/// [`DigestError::Mismatch`] instead of ending if the content does not match. The whole content is needed, so
+/// downloads starting at an offset are rejected.
+pub fn verify_download(download: ReleaseAssetDownload, expected: Digest) -> Result<ReleaseAssetDownload, DigestError> {
+  if download.offset != 0 {
+    return Err(DigestError::PartialContent(download.offset));
+  }
+  let hasher = expected.hasher()?;
+  let content = stream::unfold(Some((download.content, hasher, expected)), |state| async move {
+    let (mut content, mut hasher, expected) = state?;
+    match content.next().await {
+      Some(Ok(chunk)) => {
+        hasher.update(&chunk);
+        Some((Ok(chunk), Some((content, hasher, expected))))
+      }
+      Some(Err(e)) => Some((Err(e), None)),
+      None => {
+        let actual = hasher.finalize();
+        if actual == expected {
+          None
+        } else {
+          let e = io::Error::new(
+            io::ErrorKind::InvalidData,
+            DigestError::Mismatch {
+              expected: Box::new(expected),
+              actual: Box::new(actual),
+            },
+          );
+          Some((Err(e), None))
+        }
+      }
+    }
+  });
+  Ok(ReleaseAssetDownload {
+    content: Box::pin(content),
+    ..download
+  })
+}
+
+/// Download the asset of `query`, verifying its content.
+///
+/// The expected digest is the one reported by GitHub for the asset. For older assets without digest, it is read from
+/// the checksum manifest of `release` (see [`Release::checksum_manifest`]).
+///
+/// As the download errors of `client` may borrow the query, the context and string types must be `'static`.
+///
+/// The whole content is needed to check the digest, so queries with a non-zero `offset` are rejected with
+/// [`DigestError::PartialContent`] before sending any request.
+pub async fn download_verified<C, Cx, Str, E>(
+  client: &mut C,
+  release: &Release,
+  query: &DownloadReleaseAssetQuery<Cx, Str>,
+) -> Result<ReleaseAssetDownload, VerifyError<E>>
+where
+  Cx: Clone,
+  Str: Clone,
+  for<'a> &'a mut C: GithubClient<Cx, Str>,
+  for<'a, 'r> <&'a mut C as GithubClient<Cx, Str>>::DownloadReleaseAssetError<'r>: Into<E>,
+{
+  if let Some(offset) = query.offset.filter(|offset| *offset > 0) {
+    return Err(DigestError::PartialContent(offset).into());
+  }
+  let asset = release
+    .assets
+    .iter()
+    .find(|asset| asset.id == query.asset)
+    .ok_or(VerifyError::UnknownAsset(query.asset))?;
+  let expected = match (&asset.digest, release.checksum_manifest()) {
+    (Some(digest), _) => digest.clone(),
+    (None, Some(manifest)) if manifest.id != asset.id => {
+      let manifest_query = DownloadReleaseAssetQuery {
+        context: query.context.clone(),
+        auth: query.auth.clone(),
+        repository: query.repository.clone(),
+        asset: manifest.id,
+        offset: None,
+      };
+      let download = client
+        .download_release_asset(&manifest_query)
+        .await
+        .map_err(|e| VerifyError::Download(e.into()))?;
+      let manifest = read_manifest(download).await?;
+      manifest
+        .get(&asset.name)
+        .cloned()
+        .ok_or_else(|| VerifyError::MissingDigest(asset.name.clone()))?
+    }
+    _ => return Err(VerifyError::MissingDigest(asset.name.clone())),
+  };
+  // Fail early, before sending the request
+  expected.hasher()?;
+  let download = client
+    .download_release_asset(query)
+    .await
+    .map_err(|e| VerifyError::Download(e.into()))?;
+  Ok(verify_download(download, expected)?)
+}
+
+async fn read_manifest<E>(download: ReleaseAssetDownload) -> Result<ChecksumManifest, VerifyError<E>> {
+  let mut raw = Vec::new();
+  let mut content = download.content;
+  while let Some(chunk) = content.try_next().await.map_err(VerifyError::ReadManifest)? {
+    raw.extend_from_slice(&chunk);
+    if raw.len() as u64 > MAX_MANIFEST_SIZE {
+      return Err(VerifyError::ReadManifest(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "checksum manifest is too large",
+      )));
+    }
+  }
+  let raw =
+    String::from_utf8(raw).map_err(|e| VerifyError::ReadManifest(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+  Ok(ChecksumManifest::parse(&raw)?)
+}
+
+#[cfg(all(test, feature = "fake"))]
+mod test {
+  use super::*;
+  use crate::client::http::HttpGithubClientError;
+  use crate::common::project::RepositoryRef;
+  use crate::fake::FakeGithub;
+  use crate::query::upload_release_asset::UploadReleaseAssetQuery;
+  use crate::GithubAuth;
+  use bytes::Bytes;
+  use compact_str::CompactString;
+  use tower_service::Service;
+
+  const HELLO_SHA256: &str = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+
+  #[tokio::test]
+  async fn verify_with_checksum_manifest() {
+    let github = FakeGithub::new();
+    let id = github
+      .load_releases(
+        "octocat",
+        "Hello-World",
+        "./test-resources/fake/octocat-hello-world/releases.json",
+      )
+      .unwrap();
+    let release = github.releases(id).pop().unwrap();
+    let asset = release.assets[0].clone();
+    assert_eq!(asset.digest, None);
+    github.set_asset_content(asset.id, Bytes::from_static(b"hello"));
+    let mut upload = UploadReleaseAssetQuery::new(
+      release.upload_url,
+      CompactString::new("SHA256SUMS"),
+      CompactString::new("text/plain"),
+      Bytes::from(format!("{HELLO_SHA256}  {}\n", asset.name)),
+    );
+    upload.auth = Some(GithubAuth::Bearer(CompactString::new("ghp_fake")));
+    let mut client = github.clone();
+    client.call(&upload).await.unwrap();
+    let release = github.releases(id).pop().unwrap();
+    let query = DownloadReleaseAssetQuery::<_, CompactString>::new(RepositoryRef::Id(id), asset.id);
+
+    let download = download_verified::<_, _, _, HttpGithubClientError>(&mut client, &release, &query)
+      .await
+      .unwrap();
+    let content: Vec<Bytes> = download.content.try_collect().await.unwrap();
+    assert_eq!(content.concat(), b"hello");
+
+    github.set_asset_content(asset.id, Bytes::from_static(b"hullo"));
+    let download = download_verified::<_, _, _, HttpGithubClientError>(&mut client, &release, &query)
+      .await
+      .unwrap();
+    let e = download.content.try_collect::<Vec<Bytes>>().await.unwrap_err();
+    assert_eq!(e.kind(), io::ErrorKind::InvalidData);
+  }
+
+  #[tokio::test]
+  async fn reject_offset_before_download() {
+    let github = FakeGithub::new();
+    let id = github
+      .load_releases(
+        "octocat",
+        "Hello-World",
+        "./test-resources/fake/octocat-hello-world/releases.json",
+      )
+      .unwrap();
+    let release = github.releases(id).pop().unwrap();
+    let mut query = DownloadReleaseAssetQuery::<_, CompactString>::new(RepositoryRef::Id(id), release.assets[0].id);
+    query.offset = Some(6);
+
+    // The asset has no content, so downloading it would fail with a different error
+    let mut client = github.clone();
+    let e = download_verified::<_, _, _, HttpGithubClientError>(&mut client, &release, &query)
+      .await
+      .unwrap_err();
+    assert!(matches!(e, VerifyError::Digest(DigestError::PartialContent(6))));
+  }
+}